@@ -0,0 +1,79 @@
+use crate::engine::cell::Type;
+
+/// What gets painted on the board with the mouse.
+pub struct Brush {
+    pub cell_type: Type,
+    /// Width of a square brush, diameter of a round one, in cells.
+    pub size: usize,
+    pub shape: BrushShape,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BrushShape {
+    Square,
+    Circle,
+}
+
+impl Brush {
+    pub const MAX_SIZE: usize = 64;
+
+    pub fn new() -> Self {
+        Self {
+            cell_type: Type::TYPES[0],
+            size: 1,
+            shape: BrushShape::Square,
+        }
+    }
+
+    /// Cells covered by the brush centered on (x, y).
+    ///
+    /// Loops back on the sides and on the top, like the board does.
+    pub fn cells(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let mut cells: Vec<(usize, usize)> = Vec::new();
+        let size = self.size as i64;
+        let radius = size as f64 / 2.0;
+        // Even brushes are centered between cells
+        let center_offset = if size % 2 == 0 { 0.5 } else { 0.0 };
+        for y_offset in -size / 2..size - size / 2 {
+            for x_offset in -size / 2..size - size / 2 {
+                if self.shape == BrushShape::Circle {
+                    // Measured from the center of the cell
+                    let distance =
+                        (x_offset as f64 + center_offset).hypot(y_offset as f64 + center_offset);
+                    if distance > radius {
+                        continue;
+                    }
+                }
+                let x = (x as i64 + x_offset).rem_euclid(width as i64);
+                let y = (y as i64 + y_offset).rem_euclid(height as i64);
+                cells.push((x as usize, y as usize));
+            }
+        }
+        cells
+    }
+
+    /// Selects the next or previous type, looping around.
+    pub fn cycle_type(&mut self, forward: bool) {
+        let index = Type::TYPES
+            .iter()
+            .position(|&t| t == self.cell_type)
+            .unwrap();
+        let len = Type::TYPES.len();
+        self.cell_type = Type::TYPES[if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        }];
+    }
+
+    pub fn toggle_shape(&mut self) {
+        self.shape = match self.shape {
+            BrushShape::Square => BrushShape::Circle,
+            BrushShape::Circle => BrushShape::Square,
+        };
+    }
+}
@@ -0,0 +1,169 @@
+use sdl2::rect::Rect;
+
+/// Part of the board shown in the window.
+///
+/// The board loops on its sides, and so does the view: panning past an edge shows the other one.
+/// Positions and sizes in pixels are relative to the viewport, the part of the window the board
+/// is drawn in.
+pub struct Camera {
+    /// Board position of the top left corner of the view, in cells.
+    pub x: f64,
+    pub y: f64,
+    /// How many times bigger than when fitting the whole board the cells are drawn.
+    pub zoom: f64,
+}
+
+impl Camera {
+    const ZOOM_STEP: f64 = 1.25;
+    /// The view always shows at least that many cells across.
+    const MIN_VISIBLE_CELLS: f64 = 4.0;
+
+    pub fn new() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            zoom: 1.0,
+        }
+    }
+
+    /// Size of a cell in the viewport, in pixels.
+    pub fn cell_size(
+        &self,
+        (w_view, h_view): (u32, u32),
+        (width, height): (usize, usize),
+    ) -> (f64, f64) {
+        (
+            w_view as f64 * self.zoom / width as f64,
+            h_view as f64 * self.zoom / height as f64,
+        )
+    }
+
+    /// Moves the view by the given amount of cells, looping around the board.
+    pub fn pan(&mut self, (dx, dy): (f64, f64), (width, height): (usize, usize)) {
+        self.x = (self.x + dx).rem_euclid(width as f64);
+        self.y = (self.y + dy).rem_euclid(height as f64);
+    }
+
+    /// Zooms in (`steps` > 0) or out, keeping the board position under `anchor` in place.
+    pub fn zoom_at(
+        &mut self,
+        steps: i32,
+        anchor: (i32, i32),
+        view: (u32, u32),
+        board: (usize, usize),
+    ) {
+        let (w_cell, h_cell) = self.cell_size(view, board);
+        let max_zoom = (board.0.min(board.1) as f64 / Self::MIN_VISIBLE_CELLS).max(1.0);
+        self.zoom = (self.zoom * Self::ZOOM_STEP.powi(steps)).clamp(1.0, max_zoom);
+        let (new_w_cell, new_h_cell) = self.cell_size(view, board);
+        self.pan(
+            (
+                anchor.0 as f64 / w_cell - anchor.0 as f64 / new_w_cell,
+                anchor.1 as f64 / h_cell - anchor.1 as f64 / new_h_cell,
+            ),
+            board,
+        );
+    }
+
+    /// The cell shown at the given viewport position.
+    pub fn cell_at(
+        &self,
+        (x, y): (i32, i32),
+        view: (u32, u32),
+        board: (usize, usize),
+    ) -> (usize, usize) {
+        let (w_cell, h_cell) = self.cell_size(view, board);
+        (
+            (self.x + x as f64 / w_cell)
+                .floor()
+                .rem_euclid(board.0 as f64) as usize,
+            (self.y + y as f64 / h_cell)
+                .floor()
+                .rem_euclid(board.1 as f64) as usize,
+        )
+    }
+
+    /// Cells visible in the viewport, along with where they are drawn.
+    pub fn visible_cells(
+        &self,
+        view: (u32, u32),
+        board: (usize, usize),
+    ) -> Vec<((usize, usize), Rect)> {
+        let (w_cell, h_cell) = self.cell_size(view, board);
+        let columns = Self::spans(self.x, view.0 as f64 / w_cell, board.0, w_cell);
+        let rows = Self::spans(self.y, view.1 as f64 / h_cell, board.1, h_cell);
+
+        let mut cells: Vec<((usize, usize), Rect)> = Vec::new();
+        for &(y_start, y_count, top) in rows.iter() {
+            for y in y_start..y_start + y_count {
+                let (y_pixel, height) = Self::pixel_span(top, y - y_start, 1, h_cell);
+                for &(x_start, x_count, left) in columns.iter() {
+                    for x in x_start..x_start + x_count {
+                        let (x_pixel, width) = Self::pixel_span(left, x - x_start, 1, w_cell);
+                        if width > 0 && height > 0 {
+                            cells.push(((x, y), Rect::new(x_pixel, y_pixel, width, height)));
+                        }
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Parts of a one pixel per cell image of the board to copy to the viewport, along with
+    /// where, to show the view. There are several of them when the view crosses an edge.
+    ///
+    /// The parts cover whole cells, so they may stick out of the viewport.
+    pub fn copy_regions(&self, view: (u32, u32), board: (usize, usize)) -> Vec<(Rect, Rect)> {
+        let (w_cell, h_cell) = self.cell_size(view, board);
+        let columns = Self::spans(self.x, view.0 as f64 / w_cell, board.0, w_cell);
+        let rows = Self::spans(self.y, view.1 as f64 / h_cell, board.1, h_cell);
+
+        let mut regions: Vec<(Rect, Rect)> = Vec::new();
+        for &(y_start, y_count, top) in rows.iter() {
+            let (y_pixel, height) = Self::pixel_span(top, 0, y_count, h_cell);
+            for &(x_start, x_count, left) in columns.iter() {
+                let (x_pixel, width) = Self::pixel_span(left, 0, x_count, w_cell);
+                regions.push((
+                    Rect::new(
+                        x_start as i32,
+                        y_start as i32,
+                        x_count as u32,
+                        y_count as u32,
+                    ),
+                    Rect::new(x_pixel, y_pixel, width, height),
+                ));
+            }
+        }
+        regions
+    }
+
+    /// Splits the visible cells of one axis into runs that don't cross the board's edge.
+    ///
+    /// Each run is its first cell, its length and where it starts in the viewport, in pixels.
+    fn spans(start: f64, visible: f64, size: usize, cell: f64) -> Vec<(usize, usize, f64)> {
+        let mut spans: Vec<(usize, usize, f64)> = Vec::new();
+        let first = start.floor();
+        // The first and last cells may only be partly visible
+        let mut remaining = ((start + visible).ceil() - first) as usize;
+        let mut index = first as usize % size;
+        let mut offset = (first - start) * cell;
+        while remaining > 0 {
+            let count = remaining.min(size - index);
+            spans.push((index, count, offset));
+            offset += count as f64 * cell;
+            remaining -= count;
+            index = 0;
+        }
+        spans
+    }
+
+    /// Pixel position and length of `count` cells, starting `skip` cells after `offset`.
+    ///
+    /// Rounding is done on both ends, so neighboring cells never leave gaps between them.
+    fn pixel_span(offset: f64, skip: usize, count: usize, cell: f64) -> (i32, u32) {
+        let start = (offset + skip as f64 * cell).floor();
+        let end = (offset + (skip + count) as f64 * cell).floor();
+        (start as i32, (end - start) as u32)
+    }
+}
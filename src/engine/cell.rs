@@ -1,4 +1,7 @@
 use crate::engine::game::Game;
+use crate::engine::genome::Genome;
+use crate::engine::rules::MovePolicy;
+use rand::Rng;
 use std::collections::HashMap;
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
@@ -45,6 +48,32 @@ impl Type {
         Self::Fairy,
     ];
 
+    /// Parses a type from its name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::TYPES
+            .into_iter()
+            .find(|t| format!("{t:?}").eq_ignore_ascii_case(name))
+    }
+
+    /// Inverse Battle version of a type table: 2x becomes 0.5x, 0.5x and 0x become 2x.
+    pub fn inverse_table(
+        table: &HashMap<Self, HashMap<Self, f32>>,
+    ) -> HashMap<Self, HashMap<Self, f32>> {
+        let mut inverse: HashMap<Self, HashMap<Self, f32>> = HashMap::new();
+        for (&attack, hmap) in table.iter() {
+            let mut inverse_hmap: HashMap<Self, f32> = HashMap::new();
+            for (&defense, &multiplier) in hmap.iter() {
+                if multiplier > 1.0 {
+                    inverse_hmap.insert(defense, 0.5);
+                } else if multiplier < 1.0 {
+                    inverse_hmap.insert(defense, 2.0);
+                }
+            }
+            inverse.insert(attack, inverse_hmap);
+        }
+        inverse
+    }
+
     /// Pokemon types weaknesses and strengths.
     ///
     /// If a type doesn't have an entry for another type in its HashMap,
@@ -262,31 +291,130 @@ impl Type {
     }
 }
 
+/// How many moves a cell can know at most.
+pub const MAX_MOVES: usize = 4;
+
 #[derive(Debug, Copy, Clone)]
 pub struct Cell {
     pub cell_type: Type,
+    /// Types of the moves the cell can attack with.
+    /// The first one is always the cell's own type.
+    pub moves: [Option<Type>; MAX_MOVES],
+    /// Type gained through evolution, if any.
+    pub second_type: Option<Type>,
     pub health: f32,
-    pub changed: bool,
+    pub max_health: f32,
+    /// Damage multiplier of the cell's attacks.
+    pub attack: f32,
+    /// How many cells this one has conquered.
+    pub conquests: u32,
+    /// Evolution stage, 0 being unevolved.
+    pub stage: u8,
+    /// Heritable traits, only used when genomes are enabled.
+    pub genome: Option<Genome>,
+    /// Defensive type taken when terastallizing, replacing the cell's own types.
+    /// The cell keeps attacking with its original moves.
+    pub tera_type: Option<Type>,
+    /// Tick the cell appeared at.
+    pub born: u64,
 }
 
 impl Cell {
     pub fn new(cell_type: Type) -> Self {
         Self {
             cell_type,
+            moves: [Some(cell_type), None, None, None],
+            second_type: None,
             health: 5.0,
-            changed: true,
+            max_health: 5.0,
+            attack: 1.0,
+            conquests: 0,
+            stage: 0,
+            genome: None,
+            tera_type: None,
+            born: 0,
         }
     }
 
-    pub fn get_damage(game: &Game, c1: Self, c2: Self) -> &f32 {
-        if let Some(hmap) = game.type_table.get(&c1.cell_type) {
-            if let Some(damage) = hmap.get(&c2.cell_type) {
-                damage
+    /// Gives the cell a genome, scaling its health with the genome's defense.
+    pub fn set_genome(&mut self, genome: Genome) {
+        self.genome = Some(genome);
+        self.max_health *= genome.defense;
+        self.health = self.max_health;
+    }
+
+    /// Multiplier of a move of type `attack` against a cell of type `defense`.
+    pub fn get_multiplier(game: &Game, attack: Type, defense: Type) -> f32 {
+        if let Some(hmap) = game.type_table.get(&attack) {
+            if let Some(multiplier) = hmap.get(&defense) {
+                *multiplier
             } else {
-                &1.0
+                1.0
             }
         } else {
-            &1.0
+            1.0
+        }
+    }
+
+    /// Multiplier of a move of type `attack` against all the types of `defender`.
+    pub fn get_defense_multiplier(game: &Game, attack: Type, defender: Self) -> f32 {
+        if let Some(tera_type) = defender.tera_type {
+            return Self::get_multiplier(game, attack, tera_type);
         }
+        let mut multiplier = Self::get_multiplier(game, attack, defender.cell_type);
+        if let Some(second_type) = defender.second_type {
+            multiplier *= Self::get_multiplier(game, attack, second_type);
+        }
+        multiplier
+    }
+
+    /// Types the cell can attack with: its moves, and its second type if it has one.
+    pub fn known_moves(&self) -> Vec<Type> {
+        let mut known: Vec<Type> = self.moves.iter().flatten().copied().collect();
+        if let Some(second_type) = self.second_type {
+            known.push(second_type);
+        }
+        known
+    }
+
+    /// The move `c1` would rather use against `c2`: its own type with the first move policy,
+    /// its best move otherwise.
+    pub fn preferred_move(game: &Game, c1: Self, c2: Self) -> Type {
+        if game.rules.move_policy == MovePolicy::First {
+            return c1.cell_type;
+        }
+        let mut best = c1.cell_type;
+        for attack in c1.known_moves() {
+            if Self::get_defense_multiplier(game, attack, c2)
+                > Self::get_defense_multiplier(game, best, c2)
+            {
+                best = attack;
+            }
+        }
+        best
+    }
+
+    /// Picks the move `c1` uses against `c2`, according to the game's move policy.
+    pub fn choose_move(game: &mut Game, c1: Self, c2: Self) -> Type {
+        match game.rules.move_policy {
+            MovePolicy::Random => {
+                let known = c1.known_moves();
+                known[game.rng.gen_range(0..known.len())]
+            }
+            MovePolicy::Best | MovePolicy::First => Self::preferred_move(game, c1, c2),
+        }
+    }
+
+    pub fn get_damage(game: &mut Game, c1: Self, c2: Self) -> f32 {
+        let attack = Self::choose_move(game, c1, c2);
+        Self::get_damage_with(game, attack, c1, c2)
+    }
+
+    /// Damage dealt by `c1` to `c2` with a move of type `attack`.
+    pub fn get_damage_with(game: &Game, attack: Type, c1: Self, c2: Self) -> f32 {
+        Self::get_defense_multiplier(game, attack, c2)
+            * game.cycle_modifier(attack)
+            * c1.attack
+            * c1.genome.map_or(1.0, |g| g.attack)
     }
 }
@@ -0,0 +1,113 @@
+use crate::engine::cell::Type;
+use std::collections::HashMap;
+
+/// A repeating schedule of phases, like day and night or the seasons.
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    pub name: String,
+    pub phases: Vec<Phase>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Phase {
+    pub name: String,
+    /// How many ticks the phase lasts.
+    pub ticks: u64,
+    /// Damage multipliers of moves of the given types during this phase.
+    pub modifiers: HashMap<Type, f32>,
+}
+
+impl Cycle {
+    /// The phase the cycle is in at the given tick.
+    pub fn phase_at(&self, tick: u64) -> &Phase {
+        let length: u64 = self.phases.iter().map(|phase| phase.ticks).sum();
+        let mut tick = tick % length;
+        for phase in self.phases.iter() {
+            if tick < phase.ticks {
+                return phase;
+            }
+            tick -= phase.ticks;
+        }
+        unreachable!("the tick was wrapped around the cycle's length")
+    }
+
+    /// Day and night, then the four seasons.
+    pub fn default_schedule() -> Vec<Self> {
+        Self::parse_schedule(
+            "cycle time\n\
+            day 300 fire=1.2 grass=1.2\n\
+            night 300 dark=1.5 ghost=1.5\n\
+            cycle season\n\
+            spring 1000 grass=1.5 bug=1.2\n\
+            summer 1000 fire=1.5\n\
+            autumn 1000 ground=1.3 ghost=1.2\n\
+            winter 1000 ice=1.5",
+        )
+        .unwrap()
+    }
+
+    /// Parses a schedule, made of cycles listing their phases in order:
+    /// ```text
+    /// # Comments start with a hash
+    /// cycle time
+    /// day 300
+    /// night 300 dark=1.5 ghost=1.5
+    /// ```
+    /// A phase line is its name, how many ticks it lasts, then its type modifiers.
+    pub fn parse_schedule(text: &str) -> Result<Vec<Self>, String> {
+        let mut cycles: Vec<Self> = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let mut words = line.split_whitespace();
+            let Some(first) = words.next() else {
+                continue;
+            };
+            if first.starts_with('#') {
+                continue;
+            }
+
+            if first == "cycle" {
+                let name = words
+                    .next()
+                    .ok_or(format!("Line {number}: a cycle needs a name"))?;
+                cycles.push(Self {
+                    name: name.to_string(),
+                    phases: Vec::new(),
+                });
+                continue;
+            }
+
+            let cycle = cycles.last_mut().ok_or(format!(
+                "Line {number}: phase {first} is outside of a cycle"
+            ))?;
+            let ticks = match words.next().map(|t| t.parse::<u64>()) {
+                Some(Ok(t)) if t > 0 => t,
+                _ => {
+                    return Err(format!(
+                        "Line {number}: phase {first} needs a non zero duration"
+                    ))
+                }
+            };
+            let mut modifiers: HashMap<Type, f32> = HashMap::new();
+            for word in words {
+                let modifier = word
+                    .split_once('=')
+                    .and_then(|(t, m)| Some((Type::from_name(t)?, m.parse::<f32>().ok()?)));
+                match modifier {
+                    Some((t, m)) => modifiers.insert(t, m),
+                    None => return Err(format!("Line {number}: invalid modifier {word}")),
+                };
+            }
+            cycle.phases.push(Phase {
+                name: first.to_string(),
+                ticks,
+                modifiers,
+            });
+        }
+
+        if let Some(cycle) = cycles.iter().find(|cycle| cycle.phases.is_empty()) {
+            return Err(format!("Cycle {} has no phases", cycle.name));
+        }
+        Ok(cycles)
+    }
+}
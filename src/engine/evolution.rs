@@ -0,0 +1,67 @@
+use crate::engine::cell::Type;
+use std::collections::HashMap;
+
+/// Evolution table: what a cell becomes after enough conquests.
+#[derive(Debug, Clone)]
+pub struct Evolution {
+    /// Stages a cell goes through, in order. Stage 0 (unevolved) is implicit.
+    pub stages: Vec<Stage>,
+    /// Second type a cell of a given type gains on its first evolution.
+    pub second_types: HashMap<Type, Type>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stage {
+    /// Total conquests needed to reach this stage.
+    pub conquests: u32,
+    /// Damage multiplier of the cell's attacks.
+    pub attack: f32,
+    pub max_health: f32,
+}
+
+impl Evolution {
+    /// Builds a table from conquest thresholds, each stage being stronger than the last.
+    pub fn from_thresholds(thresholds: &[u32]) -> Self {
+        let mut stages: Vec<Stage> = Vec::new();
+        for (i, &conquests) in thresholds.iter().enumerate() {
+            let stage = (i + 1) as f32;
+            stages.push(Stage {
+                conquests,
+                attack: 1.0 + 0.25 * stage,
+                max_health: 5.0 * (1.0 + 0.5 * stage),
+            });
+        }
+        Self {
+            stages,
+            second_types: Self::second_type_table(),
+        }
+    }
+
+    /// The stage reached with that many conquests, 0 being unevolved.
+    pub fn stage_for(&self, conquests: u32) -> u8 {
+        self.stages
+            .iter()
+            .take_while(|stage| conquests >= stage.conquests)
+            .count() as u8
+    }
+
+    /// Second types gained on evolution, loosely based on famous evolution lines.
+    ///
+    /// Types without an entry stay single-typed.
+    pub fn second_type_table() -> HashMap<Type, Type> {
+        let mut table: HashMap<Type, Type> = HashMap::new();
+        table.insert(Type::Normal, Type::Flying); // Pidgeot
+        table.insert(Type::Fire, Type::Flying); // Charizard
+        table.insert(Type::Water, Type::Flying); // Gyarados
+        table.insert(Type::Grass, Type::Poison); // Venusaur
+        table.insert(Type::Electric, Type::Steel); // Magneton
+        table.insert(Type::Fighting, Type::Steel); // Lucario
+        table.insert(Type::Bug, Type::Flying); // Butterfree
+        table.insert(Type::Rock, Type::Ground); // Golem
+        table.insert(Type::Ghost, Type::Poison); // Gengar
+        table.insert(Type::Psychic, Type::Fairy); // Gardevoir
+        table.insert(Type::Dragon, Type::Flying); // Dragonite
+        table.insert(Type::Dark, Type::Fire); // Houndoom
+        table
+    }
+}
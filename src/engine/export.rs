@@ -0,0 +1,296 @@
+use crate::engine::game::Game;
+use crate::engine::palette::Palette;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+
+/// Image of the board colored by type, one pixel per cell, three bytes per pixel.
+pub fn board_pixels(game: &Game, palette: &Palette) -> Vec<u8> {
+    game.board
+        .cells
+        .iter()
+        .flat_map(|&cell| {
+            let color = palette.cell_color(cell);
+            [color.r, color.g, color.b]
+        })
+        .collect()
+}
+
+/// Scales an image up, each pixel becoming a `scale` by `scale` square.
+pub fn scale_up(pixels: &[u8], (width, height): (usize, usize), scale: usize) -> Vec<u8> {
+    let mut scaled = Vec::with_capacity(pixels.len() * scale * scale);
+    for row in pixels.chunks(width * 3).take(height) {
+        let scaled_row: Vec<u8> = row
+            .chunks(3)
+            .flat_map(|pixel| pixel.repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            scaled.extend_from_slice(&scaled_row);
+        }
+    }
+    scaled
+}
+
+/// Saves an image of the given size, three bytes per pixel, as a PNG file, scaled up `scale`
+/// times.
+pub fn save_png(
+    pixels: &[u8],
+    (width, height): (usize, usize),
+    scale: usize,
+    path: &str,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        (width * scale) as u32,
+        (height * scale) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    let data = scale_up(pixels, (width, height), scale);
+    writer.write_image_data(&data).map_err(|e| e.to_string())
+}
+
+/// Saves images of the board as the game goes, whatever the frontend.
+///
+/// Captures run on the simulation's thread and see the board after every tick, so the same seed
+/// always gives the same images, however fast the game runs or the frames are drawn.
+pub trait Capture: Send {
+    /// Looks at the board, first as it starts then after every tick.
+    ///
+    /// Returns a message for the user, if any.
+    fn observe(&mut self, game: &Game) -> Option<String>;
+
+    /// Called once the game quits, returns a message for the user, if any.
+    fn finish(&mut self) -> Option<String> {
+        None
+    }
+}
+
+/// Saves a PNG screenshot of the board at a given tick.
+pub struct Screenshot {
+    /// Tick to take the screenshot at.
+    pub at: u64,
+    /// Size of a cell in the screenshot, in pixels.
+    pub scale: usize,
+    pub palette: Palette,
+}
+
+impl Capture for Screenshot {
+    fn observe(&mut self, game: &Game) -> Option<String> {
+        if game.tick_count != self.at {
+            return None;
+        }
+        let path = format!("screenshot_{}.png", game.tick_count);
+        let size = (game.board.width, game.board.height);
+        let message = match save_png(&board_pixels(game, &self.palette), size, self.scale, &path) {
+            Ok(()) => format!("Saved {path}"),
+            Err(e) => format!("Could not save {path}: {e}"),
+        };
+        Some(message)
+    }
+}
+
+/// How long each frame of a recording is shown, in milliseconds.
+const RECORDING_FRAME_MS: u16 = 50;
+
+/// File format of a recording.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AnimationFormat {
+    Gif,
+    /// Animated PNG.
+    Apng,
+}
+
+impl AnimationFormat {
+    /// The format matching the file's extension, if any.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            "gif" => Some(Self::Gif),
+            "png" | "apng" => Some(Self::Apng),
+            _ => None,
+        }
+    }
+}
+
+/// Encoder a recording writes its frames to as they are taken.
+enum Encoder {
+    /// Pixels are looked up in a palette made of every color a cell can have.
+    Gif(gif::Encoder<BufWriter<File>>, HashMap<[u8; 3], u8>),
+    Apng(png::Writer<BufWriter<File>>),
+}
+
+/// Records the board to an animated image, every `interval` ticks.
+///
+/// Frames are written to the file as they are taken, which is finished once the recording is
+/// full or the game quits.
+pub struct Recording {
+    path: String,
+    /// How many ticks go by between two frames.
+    pub interval: u64,
+    /// Number of frames after which the recording is finished, if any.
+    pub max_frames: Option<usize>,
+    palette: Palette,
+    /// Size of the board, in cells.
+    size: (usize, usize),
+    /// Size of a cell in the recording, in pixels.
+    scale: usize,
+    /// Where frames go, until the recording is finished.
+    encoder: Option<Encoder>,
+    /// Frames written so far.
+    frames: usize,
+}
+
+impl Recording {
+    /// Starts recording a board of the given size to a file, as a GIF or an APNG depending on
+    /// its extension.
+    pub fn new(
+        path: String,
+        (width, height): (usize, usize),
+        scale: usize,
+        palette: Palette,
+    ) -> Result<Self, String> {
+        let format = AnimationFormat::from_path(&path)
+            .ok_or(format!("{path} is neither a .gif nor a .png file"))?;
+        let file = BufWriter::new(File::create(&path).map_err(|e| e.to_string())?);
+        let encoder = match format {
+            AnimationFormat::Gif => {
+                let too_big = |_| format!("{path} would be bigger than 65535 pixels");
+                let w_gif = u16::try_from(width * scale).map_err(too_big)?;
+                let h_gif = u16::try_from(height * scale).map_err(too_big)?;
+                let colors = palette.cell_colors();
+                let global_palette: Vec<u8> = colors.iter().flat_map(|c| [c.r, c.g, c.b]).collect();
+                let indices = colors
+                    .iter()
+                    .enumerate()
+                    .map(|(index, c)| ([c.r, c.g, c.b], index as u8))
+                    .collect();
+                let mut encoder = gif::Encoder::new(file, w_gif, h_gif, &global_palette)
+                    .map_err(|e| e.to_string())?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(|e| e.to_string())?;
+                Encoder::Gif(encoder, indices)
+            }
+            AnimationFormat::Apng => {
+                let mut encoder =
+                    png::Encoder::new(file, (width * scale) as u32, (height * scale) as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                let png_error = |e: png::EncodingError| e.to_string();
+                // The number of frames is only known at the end, it is written then
+                encoder.set_animated(u32::MAX, 0).map_err(png_error)?;
+                encoder
+                    .set_frame_delay(RECORDING_FRAME_MS, 1000)
+                    .map_err(png_error)?;
+                Encoder::Apng(encoder.write_header().map_err(png_error)?)
+            }
+        };
+        Ok(Self {
+            path,
+            interval: 1,
+            max_frames: None,
+            palette,
+            size: (width, height),
+            scale,
+            encoder: Some(encoder),
+            frames: 0,
+        })
+    }
+
+    /// Writes the board as the next frame.
+    fn write_frame(&mut self, game: &Game) -> Result<(), String> {
+        let (width, height) = self.size;
+        let pixels = scale_up(&board_pixels(game, &self.palette), self.size, self.scale);
+        match self.encoder.as_mut() {
+            Some(Encoder::Gif(encoder, indices)) => {
+                let indexed: Vec<u8> = pixels
+                    .chunks(3)
+                    .map(|pixel| indices[&[pixel[0], pixel[1], pixel[2]]])
+                    .collect();
+                let mut frame = gif::Frame::from_indexed_pixels(
+                    (width * self.scale) as u16,
+                    (height * self.scale) as u16,
+                    indexed,
+                    None,
+                );
+                // GIF delays are in hundredths of a second
+                frame.delay = RECORDING_FRAME_MS / 10;
+                encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+            }
+            Some(Encoder::Apng(writer)) => {
+                writer
+                    .write_image_data(&pixels)
+                    .map_err(|e| e.to_string())?;
+            }
+            None => return Ok(()),
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Finishes the file, ending the recording.
+    fn finish_file(&mut self) -> Result<(), String> {
+        match self.encoder.take() {
+            Some(Encoder::Gif(encoder, _)) => {
+                encoder
+                    .into_inner()
+                    .map_err(|e| e.to_string())?
+                    .flush()
+                    .map_err(|e| e.to_string())?;
+            }
+            Some(Encoder::Apng(writer)) => {
+                writer.finish().map_err(|e| e.to_string())?;
+                set_apng_frame_count(&self.path, self.frames as u32).map_err(|e| e.to_string())?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Finishes the file, describing how it went.
+    fn save(&mut self) -> String {
+        match self.finish_file() {
+            Ok(()) => format!("Saved {} frames to {}", self.frames, self.path),
+            Err(e) => format!("Could not save {}: {e}", self.path),
+        }
+    }
+}
+
+impl Capture for Recording {
+    fn observe(&mut self, game: &Game) -> Option<String> {
+        if self.encoder.is_none() || !game.tick_count.is_multiple_of(self.interval) {
+            return None;
+        }
+        if let Err(e) = self.write_frame(game) {
+            self.encoder = None;
+            return Some(format!("Could not record to {}: {e}", self.path));
+        }
+        (self.max_frames == Some(self.frames)).then(|| self.save())
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        self.encoder.is_some().then(|| self.save())
+    }
+}
+
+/// Writes the number of frames of a finished animated PNG, which must come before its frames.
+fn set_apng_frame_count(path: &str, frames: u32) -> std::io::Result<()> {
+    let mut file = File::options().read(true).write(true).open(path)?;
+    // The animation control chunk comes right after the header, well within the first bytes
+    let mut start = [0; 64];
+    file.read_exact(&mut start)?;
+    let Some(position) = start.windows(4).position(|name| name == b"acTL") else {
+        return Err(std::io::Error::other("no animation control chunk"));
+    };
+    // The chunk's name and the frame count are covered by its checksum, which comes after
+    // the number of plays
+    let mut chunk = [0; 12];
+    chunk.copy_from_slice(&start[position..position + 12]);
+    chunk[4..8].copy_from_slice(&frames.to_be_bytes());
+    file.seek(SeekFrom::Start(position as u64))?;
+    file.write_all(&chunk)?;
+    file.write_all(&crc32fast::hash(&chunk).to_be_bytes())
+}
@@ -0,0 +1,124 @@
+use sdl2::{pixels::Color, rect::Rect, render::WindowCanvas};
+
+/// Width and height of a glyph, in font pixels.
+pub const GLYPH_WIDTH: u32 = 3;
+pub const GLYPH_HEIGHT: u32 = 5;
+/// Space between two glyphs or two lines, in font pixels.
+pub const SPACING: u32 = 1;
+
+/// A tiny bitmap font, embedded so no font file is needed.
+///
+/// Covers ASCII from the space to the underscore, one row per byte, the leftmost pixel being the
+/// highest of the three bits. Lowercase letters are drawn as uppercase ones.
+const GLYPHS: [[u8; 5]; 64] = [
+    [0b000, 0b000, 0b000, 0b000, 0b000], // ' '
+    [0b010, 0b010, 0b010, 0b000, 0b010], // '!'
+    [0b101, 0b101, 0b000, 0b000, 0b000], // '"'
+    [0b101, 0b111, 0b101, 0b111, 0b101], // '#'
+    [0b011, 0b110, 0b010, 0b011, 0b110], // '$'
+    [0b101, 0b001, 0b010, 0b100, 0b101], // '%'
+    [0b010, 0b101, 0b010, 0b101, 0b011], // '&'
+    [0b010, 0b010, 0b000, 0b000, 0b000], // '\''
+    [0b001, 0b010, 0b010, 0b010, 0b001], // '('
+    [0b100, 0b010, 0b010, 0b010, 0b100], // ')'
+    [0b000, 0b101, 0b010, 0b101, 0b000], // '*'
+    [0b000, 0b010, 0b111, 0b010, 0b000], // '+'
+    [0b000, 0b000, 0b000, 0b010, 0b100], // ','
+    [0b000, 0b000, 0b111, 0b000, 0b000], // '-'
+    [0b000, 0b000, 0b000, 0b000, 0b010], // '.'
+    [0b001, 0b001, 0b010, 0b100, 0b100], // '/'
+    [0b111, 0b101, 0b101, 0b101, 0b111], // '0'
+    [0b010, 0b110, 0b010, 0b010, 0b111], // '1'
+    [0b111, 0b001, 0b111, 0b100, 0b111], // '2'
+    [0b111, 0b001, 0b111, 0b001, 0b111], // '3'
+    [0b101, 0b101, 0b111, 0b001, 0b001], // '4'
+    [0b111, 0b100, 0b111, 0b001, 0b111], // '5'
+    [0b111, 0b100, 0b111, 0b101, 0b111], // '6'
+    [0b111, 0b001, 0b001, 0b010, 0b010], // '7'
+    [0b111, 0b101, 0b111, 0b101, 0b111], // '8'
+    [0b111, 0b101, 0b111, 0b001, 0b111], // '9'
+    [0b000, 0b010, 0b000, 0b010, 0b000], // ':'
+    [0b000, 0b010, 0b000, 0b010, 0b100], // ';'
+    [0b001, 0b010, 0b100, 0b010, 0b001], // '<'
+    [0b000, 0b111, 0b000, 0b111, 0b000], // '='
+    [0b100, 0b010, 0b001, 0b010, 0b100], // '>'
+    [0b111, 0b001, 0b011, 0b000, 0b010], // '?'
+    [0b111, 0b101, 0b111, 0b100, 0b111], // '@'
+    [0b010, 0b101, 0b111, 0b101, 0b101], // 'A'
+    [0b110, 0b101, 0b110, 0b101, 0b110], // 'B'
+    [0b011, 0b100, 0b100, 0b100, 0b011], // 'C'
+    [0b110, 0b101, 0b101, 0b101, 0b110], // 'D'
+    [0b111, 0b100, 0b110, 0b100, 0b111], // 'E'
+    [0b111, 0b100, 0b110, 0b100, 0b100], // 'F'
+    [0b011, 0b100, 0b101, 0b101, 0b011], // 'G'
+    [0b101, 0b101, 0b111, 0b101, 0b101], // 'H'
+    [0b111, 0b010, 0b010, 0b010, 0b111], // 'I'
+    [0b001, 0b001, 0b001, 0b101, 0b010], // 'J'
+    [0b101, 0b101, 0b110, 0b101, 0b101], // 'K'
+    [0b100, 0b100, 0b100, 0b100, 0b111], // 'L'
+    [0b101, 0b111, 0b111, 0b101, 0b101], // 'M'
+    [0b110, 0b101, 0b101, 0b101, 0b101], // 'N'
+    [0b010, 0b101, 0b101, 0b101, 0b010], // 'O'
+    [0b110, 0b101, 0b110, 0b100, 0b100], // 'P'
+    [0b010, 0b101, 0b101, 0b110, 0b011], // 'Q'
+    [0b110, 0b101, 0b110, 0b101, 0b101], // 'R'
+    [0b011, 0b100, 0b010, 0b001, 0b110], // 'S'
+    [0b111, 0b010, 0b010, 0b010, 0b010], // 'T'
+    [0b101, 0b101, 0b101, 0b101, 0b111], // 'U'
+    [0b101, 0b101, 0b101, 0b101, 0b010], // 'V'
+    [0b101, 0b101, 0b111, 0b111, 0b101], // 'W'
+    [0b101, 0b101, 0b010, 0b101, 0b101], // 'X'
+    [0b101, 0b101, 0b010, 0b010, 0b010], // 'Y'
+    [0b111, 0b001, 0b010, 0b100, 0b111], // 'Z'
+    [0b110, 0b100, 0b100, 0b100, 0b110], // '['
+    [0b100, 0b100, 0b010, 0b001, 0b001], // '\\'
+    [0b011, 0b001, 0b001, 0b001, 0b011], // ']'
+    [0b010, 0b101, 0b000, 0b000, 0b000], // '^'
+    [0b000, 0b000, 0b000, 0b000, 0b111], // '_'
+];
+const BAR: [u8; 5] = [0b010, 0b010, 0b010, 0b010, 0b010];
+
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        c @ ' '..='_' => GLYPHS[c as usize - ' ' as usize],
+        '|' => BAR,
+        _ => GLYPHS['?' as usize - ' ' as usize],
+    }
+}
+
+/// Size of the given text in window pixels, each font pixel being `scale` pixels wide.
+pub fn text_size(text: &str, scale: u32) -> (u32, u32) {
+    let columns = text.chars().count() as u32;
+    (
+        (columns * (GLYPH_WIDTH + SPACING)).saturating_sub(SPACING) * scale,
+        GLYPH_HEIGHT * scale,
+    )
+}
+
+/// Draws a line of text with its top left corner at (x, y).
+pub fn draw_text(
+    canvas: &mut WindowCanvas,
+    text: &str,
+    (x, y): (i32, i32),
+    scale: u32,
+    color: Color,
+) {
+    canvas.set_draw_color(color);
+    let mut rects: Vec<Rect> = Vec::new();
+    for (column, c) in text.chars().enumerate() {
+        let left = x + (column as u32 * (GLYPH_WIDTH + SPACING) * scale) as i32;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for bit in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - bit)) != 0 {
+                    rects.push(Rect::new(
+                        left + (bit * scale) as i32,
+                        y + (row as u32 * scale) as i32,
+                        scale,
+                        scale,
+                    ));
+                }
+            }
+        }
+    }
+    canvas.fill_rects(&rects).unwrap();
+}
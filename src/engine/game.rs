@@ -1,38 +1,133 @@
-use crate::engine::cell::{Cell, Type};
+use crate::engine::cell::{Cell, Type, MAX_MOVES};
+use crate::engine::export::Capture;
+use crate::engine::genome::Genome;
+use crate::engine::render::{Command, Frontend};
+use crate::engine::rules::Rules;
+use crate::engine::stats::Stats;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+/// Bounds of the tick speed, speeding up past the highest one runs the game as fast as
+/// possible.
+pub const MIN_TICK_SPEED: f64 = 0.5;
+pub const MAX_TICK_SPEED: f64 = 10000.0;
+/// Bounds of the frame rate.
+pub const MIN_FRAME_RATE: f64 = 1.0;
+pub const MAX_FRAME_RATE: f64 = 1000.0;
+
+#[derive(Clone)]
 pub struct Game {
     pub board: Board,
     pub type_table: HashMap<Type, HashMap<Type, f32>>,
-    pub renderer: crate::engine::render::Renderer,
+    pub rules: Rules,
+    /// How many ticks have elapsed since the start.
+    pub tick_count: u64,
+    /// Seed of the random generator, to replay a run.
+    pub seed: u64,
+    pub rng: StdRng,
 }
 
 impl Game {
-    pub fn new((width, height): (usize, usize), (w_win, h_win): (u32, u32)) -> Self {
-        let mut cells: Vec<Cell> = Vec::new();
-        for _ in 0..width * height {
-            cells.push(Cell::new(
-                Type::TYPES[rand::random::<usize>() % Type::TYPES.len()],
-            ));
-        }
-        Self {
+    pub fn new((width, height): (usize, usize), rules: Rules, seed: u64) -> Self {
+        let mut game = Self {
             board: Board {
-                cells,
+                cells: Vec::new(),
                 width,
                 height,
+                dirty: vec![true; width * height],
+                states: vec![TickState::Ready; width * height],
+                conquest_counts: vec![0; width * height],
+            },
+            type_table: if rules.inverse {
+                Type::inverse_table(&Type::type_table())
+            } else {
+                Type::type_table()
             },
-            type_table: Type::type_table(),
-            renderer: crate::engine::render::Renderer::setup((w_win, h_win)),
+            rules,
+            tick_count: 0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        for _ in 0..width * height {
+            let cell_type = Type::TYPES[game.rng.gen_range(0..Type::TYPES.len())];
+            let cell = game.spawn_cell(cell_type);
+            game.board.cells.push(cell);
+        }
+        game
+    }
+
+    /// Creates a fresh cell of the given type, following the game's rules.
+    ///
+    /// Moves other than the cell's own type are random coverage moves.
+    pub fn spawn_cell(&mut self, cell_type: Type) -> Cell {
+        let mut cell = Cell {
+            born: self.tick_count,
+            ..Cell::new(cell_type)
+        };
+        for slot in 1..self.rules.moveset_size.min(MAX_MOVES) {
+            cell.moves[slot] = Some(Type::TYPES[self.rng.gen_range(0..Type::TYPES.len())]);
+        }
+        if self.rules.genomes {
+            cell.set_genome(Genome::random(&mut self.rng));
+        }
+        cell
+    }
+
+    /// Damage multiplier of moves of the given type, from the current phase of every cycle.
+    pub fn cycle_modifier(&self, attack: Type) -> f32 {
+        self.rules
+            .cycles
+            .iter()
+            .filter_map(|cycle| cycle.phase_at(self.tick_count).modifiers.get(&attack))
+            .product()
+    }
+
+    /// Name of the current phase of every cycle, along with the cycle's name.
+    pub fn phases(&self) -> Vec<(String, String)> {
+        self.rules
+            .cycles
+            .iter()
+            .map(|cycle| {
+                (
+                    cycle.name.clone(),
+                    cycle.phase_at(self.tick_count).name.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Creates the cell replacing one conquered by `parent`.
+    ///
+    /// It is a fresh cell of the parent's kind: same moveset, and a mutated copy of its genome.
+    pub fn offspring(&mut self, parent: Cell) -> Cell {
+        let mut cell = Cell {
+            moves: parent.moves,
+            born: self.tick_count,
+            ..Cell::new(parent.cell_type)
+        };
+        if let Some(genome) = parent.genome {
+            cell.set_genome(genome.mutate(&mut self.rng));
         }
+        cell
     }
 
-    /// A tick is when the engine updates:
-    /// - The cells, who wins and who loses
-    /// - The input (optional)
-    /// # Parameters
-    /// - Tick speed: How quick the engine updates
+    /// A tick is when the engine updates the cells, who wins and who loses.
+    ///
+    /// Which cells may fight follows these rules:
+    /// - At the start of a tick, every cell is `TickState::Ready`.
+    /// - Cells take their turn row by row, each attacking its 8 neighbors one after the other.
+    /// - A cell only attacks or gets attacked by cells of a different type.
+    /// - A conquered cell is replaced by a `TickState::Spawned` one, which neither attacks
+    ///   nor gets attacked until the next tick.
+    /// - An evolving or terastallizing cell becomes `TickState::Resting`, which also sits out
+    ///   the rest of the tick.
+    /// - A damaged cell that wasn't conquered stays ready.
     fn tick(&mut self) {
+        self.board.states.fill(TickState::Ready);
         for y in 0..self.board.height {
             for x in 0..self.board.width {
                 let mut neighbors = self.board.get_neighbors(x as i32, y as i32);
@@ -41,83 +136,450 @@ impl Game {
                 }
             }
         }
+        self.tick_count += 1;
     }
 
-    /// Goes on until the program is exited.
-    pub fn game_loop(&mut self, tick_speed: f64) {
-        for cell in self.board.cells.iter_mut() {
-            cell.changed = false;
+    /// Ticks, then prints statistics if enough ticks went by since the last ones and shows the
+    /// new board to every capture.
+    fn run_tick(&mut self, observers: &mut Observers) {
+        // let now = std::time::Instant::now();
+        self.tick();
+        // println!("Tick time: {:?}ms", now.elapsed().as_millis());
+        if let Some(interval) = observers.stats_interval {
+            if self.tick_count.is_multiple_of(interval) {
+                observers.print(Stats::collect(self).to_string());
+            }
         }
+        observers.capture(self);
+    }
+
+    /// Goes on until the program is exited, ticking no further than `until` if given.
+    ///
+    /// The simulation runs on its own thread, publishing a snapshot of the board every frame,
+    /// while this one handles the frontend's input and draws the latest snapshot, so neither a
+    /// slow tick nor a slow frame holds up the other.
+    /// The rules and the type table never change, so the renderer keeps its own copy of them.
+    /// Frames are drawn `frame_rate` times per second, or as the display refreshes with vsync.
+    /// Captures follow the simulation rather than the frames, so they see every tick.
+    /// Messages printed while the frontend draws on standard output wait until it is gone.
+    pub fn game_loop(
+        self,
+        mut frontend: Box<dyn Frontend>,
+        captures: Vec<Box<dyn Capture>>,
+        tick_speed: f64,
+        frame_rate: f64,
+        stats_interval: Option<u64>,
+        until: Option<u64>,
+    ) {
+        let frame_duration = Duration::from_secs_f64(1.0 / frame_rate);
+        let mut view = self.clone();
+        let shared = Arc::new(Mutex::new(Snapshot {
+            board: self.board.clone(),
+            fresh: false,
+            tick_count: self.tick_count,
+            tick_speed,
+            paused: false,
+        }));
+        let observers = Observers {
+            stats_interval,
+            captures,
+            held: frontend.uses_stdout().then(Vec::new),
+        };
+        let (commands, received) = mpsc::channel();
+        let simulation = {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || {
+                self.simulate(
+                    received,
+                    &shared,
+                    observers,
+                    tick_speed,
+                    frame_duration,
+                    until,
+                )
+            })
+        };
 
         'game_loop: loop {
-            // let now = std::time::Instant::now();
-            self.tick();
-            // println!("Tick time: {:?}ms", now.elapsed().as_millis());
-            // let now = std::time::Instant::now();
-            if crate::engine::render::Renderer::render_optimized(self) {
-                // Renders return true if requested to quit
-                break 'game_loop;
+            let frame_start = Instant::now();
+            // The simulation only stops by itself if it panicked, which is reported on join
+            if simulation.is_finished() {
+                break;
+            }
+            for command in frontend.poll_commands(&view) {
+                let quit = matches!(command, Command::Quit);
+                if commands.send(command).is_err() || quit {
+                    break 'game_loop;
+                }
             }
+
+            let (tick_speed, paused) = {
+                let mut latest = shared.lock().unwrap();
+                // The board drawn last is handed back, for the simulation to copy the next one in
+                if latest.fresh {
+                    std::mem::swap(&mut view.board, &mut latest.board);
+                    latest.fresh = false;
+                }
+                view.tick_count = latest.tick_count;
+                (latest.tick_speed, latest.paused)
+            };
+            // let now = std::time::Instant::now();
+            frontend.present(&view, tick_speed, paused);
+            view.board.dirty.fill(false);
             // println!("Draw time: {:?}ms", now.elapsed().as_millis());
-            std::thread::sleep(std::time::Duration::from_millis(
-                (1000.0 / tick_speed) as u64,
-            ));
+            // With vsync, presenting the frame already waited for the display
+            if !frontend.vsync() {
+                if let Some(rest) = frame_duration.checked_sub(frame_start.elapsed()) {
+                    std::thread::sleep(rest);
+                }
+            }
+        }
+        let result = simulation.join();
+        // The frontend may be hiding standard output, it is only reported to once it is gone
+        drop(frontend);
+        match result {
+            Ok(held) => {
+                for message in held {
+                    println!("{message}");
+                }
+            }
+            Err(panic) => {
+                let reason = panic
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or(panic.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown error");
+                eprintln!("The simulation crashed: {reason}");
+                std::panic::resume_unwind(panic);
+            }
+        }
+    }
+
+    /// Runs the simulation until told to quit, publishing a snapshot to `shared` every frame.
+    ///
+    /// The game ticks `tick_speed` times per second whatever the frame rate, running several
+    /// ticks per frame if needed, or as many as fit in a frame if `tick_speed` is infinite.
+    /// Once the game reached the tick `until`, it stops ticking and only waits to quit.
+    /// When quitting, captures are finished and the messages held by `observers` returned.
+    fn simulate(
+        mut self,
+        commands: Receiver<Command>,
+        shared: &Mutex<Snapshot>,
+        mut observers: Observers,
+        mut tick_speed: f64,
+        frame_duration: Duration,
+        until: Option<u64>,
+    ) -> Vec<String> {
+        let over = |game: &Game| until.is_some_and(|until| game.tick_count >= until);
+        // The first snapshot was taken from this state
+        self.board.dirty.fill(false);
+        // Board copied into the snapshot, swapped with the shared one on publishing
+        let mut spare = self.board.clone();
+        observers.capture(&self);
+        let mut paused = false;
+        // Time the game is behind, which ticks catch up on
+        let mut lag = Duration::ZERO;
+        let mut last_frame = Instant::now();
+        loop {
+            let frame_start = Instant::now();
+            lag += frame_start - last_frame;
+            last_frame = frame_start;
+            let mut step = false;
+            for command in commands.try_iter() {
+                match command {
+                    Command::Quit => return observers.finish(),
+                    Command::TogglePause => paused = !paused,
+                    Command::Step => {
+                        paused = true;
+                        step = true;
+                    }
+                    Command::SpeedUp if tick_speed * 2.0 > MAX_TICK_SPEED => {
+                        tick_speed = f64::INFINITY
+                    }
+                    Command::SpeedUp => tick_speed *= 2.0,
+                    Command::SlowDown => {
+                        tick_speed = (tick_speed / 2.0).clamp(MIN_TICK_SPEED, MAX_TICK_SPEED)
+                    }
+                    Command::Paint(cells, cell_type) => {
+                        for (x, y) in cells {
+                            let cell = self.spawn_cell(cell_type);
+                            self.board.set(x + y * self.board.width, cell);
+                        }
+                    }
+                }
+            }
+
+            if paused {
+                // Resuming must not catch up on the time spent paused
+                lag = Duration::ZERO;
+                if step && !over(&self) {
+                    self.run_tick(&mut observers);
+                }
+            } else if tick_speed.is_infinite() {
+                // At least one tick per frame, then as many as fit in the frame
+                while !over(&self) {
+                    self.run_tick(&mut observers);
+                    if frame_start.elapsed() >= frame_duration {
+                        break;
+                    }
+                }
+                lag = Duration::ZERO;
+            } else {
+                let tick_duration = Duration::from_secs_f64(1.0 / tick_speed);
+                while lag >= tick_duration && !over(&self) {
+                    self.run_tick(&mut observers);
+                    lag -= tick_duration;
+                    // Ticks slower than the requested speed give up on catching up,
+                    // rather than never publishing a snapshot again
+                    if frame_start.elapsed() >= frame_duration {
+                        lag = Duration::ZERO;
+                    }
+                }
+            }
+
+            self.publish(&mut spare, shared, tick_speed, paused);
+            if let Some(rest) = frame_duration.checked_sub(frame_start.elapsed()) {
+                std::thread::sleep(rest);
+            }
+        }
+    }
 
-            for cell in self.board.cells.iter_mut() {
-                cell.changed = false;
+    /// Replaces the shared snapshot with the current state.
+    ///
+    /// The board is copied into `spare` outside the lock, which is then only held to swap it
+    /// in. Cells changed since the renderer last took a snapshot stay marked, even if it skipped
+    /// some of them.
+    fn publish(
+        &mut self,
+        spare: &mut Board,
+        shared: &Mutex<Snapshot>,
+        tick_speed: f64,
+        paused: bool,
+    ) {
+        spare.clone_from(&self.board);
+        let mut latest = shared.lock().unwrap();
+        if latest.fresh {
+            for (dirty, &undrawn) in spare.dirty.iter_mut().zip(&latest.board.dirty) {
+                *dirty |= undrawn;
             }
         }
+        std::mem::swap(&mut latest.board, spare);
+        latest.fresh = true;
+        latest.tick_count = self.tick_count;
+        latest.tick_speed = tick_speed;
+        latest.paused = paused;
+        drop(latest);
+        self.board.dirty.fill(false);
     }
 
     fn attack_cell(&mut self, (x1, y1): (usize, usize), (x2, y2): (usize, usize)) {
         if self.board.cells[x2 + y2 * self.board.width].cell_type
             == self.board.cells[x1 + y1 * self.board.width].cell_type
-            || self.board.cells[x1 + y1 * self.board.width].changed
-            || self.board.cells[x2 + y2 * self.board.width].changed
+            || self.board.states[x1 + y1 * self.board.width] != TickState::Ready
+            || self.board.states[x2 + y2 * self.board.width] != TickState::Ready
         {
             return;
         }
+        if let Some(genome) = self.board.cells[x1 + y1 * self.board.width].genome {
+            if self.rng.gen::<f32>() >= genome.aggression {
+                return;
+            }
+        }
 
-        let damage = *Cell::get_damage(
+        let damage = Cell::get_damage(
             self,
             self.board.cells[x1 + y1 * self.board.width],
             self.board.cells[x2 + y2 * self.board.width],
         );
 
         self.board.cells[x2 + y2 * self.board.width].health -= damage;
+        self.board.mark_dirty(x2 + y2 * self.board.width);
         if self.board.cells[x2 + y2 * self.board.width].health <= 0.0 {
-            self.board.cells[x2 + y2 * self.board.width] =
-                Cell::new(self.board.cells[x1 + y1 * self.board.width].cell_type);
+            let offspring = self.offspring(self.board.cells[x1 + y1 * self.board.width]);
+            self.board.set(x2 + y2 * self.board.width, offspring);
+            self.board.states[x2 + y2 * self.board.width] = TickState::Spawned;
+            self.board.conquest_counts[x2 + y2 * self.board.width] += 1;
+            self.board.cells[x1 + y1 * self.board.width].conquests += 1;
+            self.evolve(x1 + y1 * self.board.width);
         } else {
-            self.board.cells[x2 + y2 * self.board.width].changed = false;
+            self.terastallize(x2 + y2 * self.board.width);
+        }
+    }
+
+    /// Gives the cell at `index` a chance to terastallize, if it hasn't already.
+    ///
+    /// A terastallizing cell rests for the rest of the tick.
+    fn terastallize(&mut self, index: usize) {
+        let Some(tera) = self.rules.tera else {
+            return;
+        };
+        let cell = &mut self.board.cells[index];
+        if cell.tera_type.is_none() && tera.triggers(cell.health, cell.max_health, &mut self.rng) {
+            cell.tera_type = Some(tera.pick_type(&mut self.rng));
+            self.board.states[index] = TickState::Resting;
+            self.board.mark_dirty(index);
+        }
+    }
+
+    /// Evolves the cell at `index` if it has made enough conquests.
+    ///
+    /// An evolving cell rests for the rest of the tick.
+    fn evolve(&mut self, index: usize) {
+        let Some(evolution) = &self.rules.evolution else {
+            return;
+        };
+        let cell = &mut self.board.cells[index];
+        let stage = evolution.stage_for(cell.conquests);
+        if stage <= cell.stage {
+            return;
+        }
+
+        let stats = evolution.stages[stage as usize - 1];
+        if cell.stage == 0 {
+            cell.second_type = evolution.second_types.get(&cell.cell_type).copied();
         }
+        let max_health = stats.max_health * cell.genome.map_or(1.0, |g| g.defense);
+        cell.health += max_health - cell.max_health;
+        cell.max_health = max_health;
+        cell.attack = stats.attack;
+        cell.stage = stage;
+        self.board.states[index] = TickState::Resting;
+        self.board.mark_dirty(index);
     }
 }
 
-#[derive(Clone)]
 pub struct Board {
     pub cells: Vec<Cell>,
     pub width: usize,
     pub height: usize,
+    /// Cells whose look changed since the renderer last drew them.
+    pub dirty: Vec<bool>,
+    /// Whether each cell may still fight during the current tick, see `Game::tick`.
+    pub states: Vec<TickState>,
+    /// How many times each location was conquered since the start.
+    pub conquest_counts: Vec<u32>,
+}
+
+/// Part a cell can take in the fights of the current tick.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TickState {
+    /// Attacks its neighbors on its turn, and can be attacked.
+    Ready,
+    /// Just replaced a conquered cell: sits out the rest of the tick.
+    Spawned,
+    /// Evolved or terastallized: sits out the rest of the tick too.
+    Resting,
+}
+
+impl Clone for Board {
+    fn clone(&self) -> Self {
+        Self {
+            cells: self.cells.clone(),
+            width: self.width,
+            height: self.height,
+            dirty: self.dirty.clone(),
+            states: self.states.clone(),
+            conquest_counts: self.conquest_counts.clone(),
+        }
+    }
+
+    /// Copies every field in place, so publishing a snapshot doesn't allocate.
+    fn clone_from(&mut self, source: &Self) {
+        self.cells.clone_from(&source.cells);
+        self.width = source.width;
+        self.height = source.height;
+        self.dirty.clone_from(&source.dirty);
+        self.states.clone_from(&source.states);
+        self.conquest_counts.clone_from(&source.conquest_counts);
+    }
+}
+
+/// What follows the simulation besides the renderer, on its thread.
+struct Observers {
+    /// Statistics are printed every that many ticks, if set.
+    stats_interval: Option<u64>,
+    captures: Vec<Box<dyn Capture>>,
+    /// Messages held while the frontend draws on standard output, which they would garble,
+    /// none if they are printed right away.
+    held: Option<Vec<String>>,
+}
+
+impl Observers {
+    fn print(&mut self, message: String) {
+        match &mut self.held {
+            Some(held) => held.push(message),
+            None => println!("{message}"),
+        }
+    }
+
+    /// Shows the board to every capture.
+    fn capture(&mut self, game: &Game) {
+        for index in 0..self.captures.len() {
+            if let Some(message) = self.captures[index].observe(game) {
+                self.print(message);
+            }
+        }
+    }
+
+    /// Finishes every capture, returning the messages held.
+    fn finish(mut self) -> Vec<String> {
+        for index in 0..self.captures.len() {
+            if let Some(message) = self.captures[index].finish() {
+                self.print(message);
+            }
+        }
+        self.held.unwrap_or_default()
+    }
+}
+
+/// What the simulation publishes for the renderer to draw.
+pub struct Snapshot {
+    /// Board as of `tick_count`, once `fresh` the one the renderer drew last otherwise.
+    pub board: Board,
+    /// Whether `board` was published since the renderer last took it.
+    pub fresh: bool,
+    pub tick_count: u64,
+    pub tick_speed: f64,
+    pub paused: bool,
 }
 
 impl Board {
+    /// Replaces the cell at `index`, marking it for redraw.
+    pub fn set(&mut self, index: usize, cell: Cell) {
+        self.cells[index] = cell;
+        self.dirty[index] = true;
+    }
+
+    /// Whether the cell at (x, y) has a neighbor of a different type.
+    pub fn is_front(&self, x: usize, y: usize) -> bool {
+        let cell_type = self.cells[x + y * self.width].cell_type;
+        self.neighbors(x as i32, y as i32)
+            .any(|(n_x, n_y)| self.cells[n_x + n_y * self.width].cell_type != cell_type)
+    }
+
+    /// Marks the cell at `index` for redraw, after it was modified in place.
+    pub fn mark_dirty(&mut self, index: usize) {
+        self.dirty[index] = true;
+    }
+
     /// Get all 8 neighbors of a cell
     ///
     /// Loops back on the sides and on the top, like a sphere
     pub fn get_neighbors(&self, x: i32, y: i32) -> Vec<(usize, usize)> {
-        let mut neighbors: Vec<(usize, usize)> = Vec::new();
-        for y_offset in -1..=1 {
-            for x_offset in -1..=1 {
-                if x_offset == 0 && y_offset == 0 {
-                    continue;
-                }
-                let x = (x + x_offset + self.width as i32) % self.width as i32;
-                let y = (y + y_offset + self.height as i32) % self.height as i32;
-                neighbors.push((x as usize, y as usize));
-            }
-        }
-        neighbors
+        self.neighbors(x, y).collect()
+    }
+
+    /// The neighbors of a cell as `get_neighbors` gives them, without allocating.
+    fn neighbors(&self, x: i32, y: i32) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (-1..=1)
+            .flat_map(|y_offset| (-1..=1).map(move |x_offset| (x_offset, y_offset)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(x_offset, y_offset)| {
+                let x = (x + x_offset + width) % width;
+                let y = (y + y_offset + height) % height;
+                (x as usize, y as usize)
+            })
     }
 }
 
@@ -133,3 +595,101 @@ impl Display for Board {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 seeded board of normal cells, which never fight each other.
+    fn normal_board() -> Game {
+        let mut game = Game::new((3, 3), Rules::default(), 7);
+        game.board.cells.fill(Cell::new(Type::Normal));
+        game
+    }
+
+    #[test]
+    fn spawned_cell_sits_out_the_tick() {
+        let mut game = normal_board();
+        game.board.cells[0] = Cell::new(Type::Fire);
+        game.board.cells[1] = Cell {
+            health: 1.0,
+            ..Cell::new(Type::Grass)
+        };
+
+        game.attack_cell((0, 0), (1, 0));
+        assert_eq!(game.board.cells[1].cell_type, Type::Fire);
+        assert_eq!(game.board.states[1], TickState::Spawned);
+
+        // The new fire cell would otherwise hit and be hit by its normal neighbors
+        game.attack_cell((1, 0), (1, 1));
+        game.attack_cell((1, 1), (1, 0));
+        assert_eq!(game.board.cells[4].health, 5.0);
+        assert_eq!(game.board.cells[1].health, 5.0);
+    }
+
+    #[test]
+    fn damaged_cell_stays_ready() {
+        let mut game = normal_board();
+        game.board.cells[0] = Cell::new(Type::Fire);
+        game.board.cells[1] = Cell::new(Type::Grass);
+
+        game.attack_cell((0, 0), (1, 0));
+        assert_eq!(game.board.cells[1].cell_type, Type::Grass);
+        assert!(game.board.cells[1].health < 5.0);
+        assert_eq!(game.board.states[1], TickState::Ready);
+
+        // It can still strike back during the same tick
+        game.attack_cell((1, 0), (0, 0));
+        assert!(game.board.cells[0].health < 5.0);
+    }
+
+    #[test]
+    fn same_type_neighbors_do_not_fight() {
+        let mut game = normal_board();
+        game.board.cells[0] = Cell::new(Type::Fire);
+        game.board.cells[1] = Cell::new(Type::Fire);
+
+        game.attack_cell((0, 0), (1, 0));
+        game.attack_cell((1, 0), (0, 0));
+        assert_eq!(game.board.cells[0].health, 5.0);
+        assert_eq!(game.board.cells[1].health, 5.0);
+    }
+
+    #[test]
+    fn states_reset_at_the_next_tick() {
+        let mut game = normal_board();
+        game.board.states[0] = TickState::Spawned;
+        game.board.states[1] = TickState::Resting;
+
+        game.tick();
+        assert!(game
+            .board
+            .states
+            .iter()
+            .all(|&state| state == TickState::Ready));
+        assert_eq!(game.tick_count, 1);
+    }
+
+    #[test]
+    fn front_cells_border_another_type() {
+        let mut game = normal_board();
+        assert!(!game.board.is_front(0, 0));
+        // Neighbors wrap around, so the opposite corner borders the first cell
+        game.board.set(8, Cell::new(Type::Fire));
+        assert!(game.board.is_front(0, 0));
+        assert!(game.board.is_front(1, 1));
+        assert_eq!(
+            game.board.get_neighbors(0, 0),
+            vec![
+                (2, 2),
+                (0, 2),
+                (1, 2),
+                (2, 0),
+                (1, 0),
+                (2, 1),
+                (0, 1),
+                (1, 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use rand::{rngs::StdRng, Rng};
+
+/// Heritable traits of a cell, passed on to the cells it conquers.
+#[derive(Debug, Clone, Copy)]
+pub struct Genome {
+    /// Damage multiplier.
+    pub attack: f32,
+    /// Max health multiplier.
+    pub defense: f32,
+    /// Chance to attack a neighbor, between 0 and 1.
+    pub aggression: f32,
+    /// How much each trait can drift when passed on.
+    pub mutation_rate: f32,
+}
+
+impl Genome {
+    const MIN_MULTIPLIER: f32 = 0.1;
+    const MAX_MULTIPLIER: f32 = 3.0;
+    const MAX_MUTATION_RATE: f32 = 0.5;
+
+    /// A genome close to a plain cell, with a bit of variation to select from.
+    pub fn random(rng: &mut StdRng) -> Self {
+        Self {
+            attack: 1.0,
+            defense: 1.0,
+            aggression: 1.0,
+            mutation_rate: 0.05,
+        }
+        .mutate(rng)
+    }
+
+    /// Copy of the genome where each trait drifted by up to `mutation_rate` of its value.
+    pub fn mutate(&self, rng: &mut StdRng) -> Self {
+        let mut drift = |value: f32| value * (1.0 + self.mutation_rate * rng.gen_range(-1.0..=1.0));
+        Self {
+            attack: drift(self.attack).clamp(Self::MIN_MULTIPLIER, Self::MAX_MULTIPLIER),
+            defense: drift(self.defense).clamp(Self::MIN_MULTIPLIER, Self::MAX_MULTIPLIER),
+            aggression: drift(self.aggression).clamp(0.0, 1.0),
+            mutation_rate: drift(self.mutation_rate).clamp(0.0, Self::MAX_MUTATION_RATE),
+        }
+    }
+
+    /// Trait by trait average of the given genomes.
+    pub fn average<'a>(genomes: impl Iterator<Item = &'a Genome>) -> Option<Self> {
+        let mut sum = Self {
+            attack: 0.0,
+            defense: 0.0,
+            aggression: 0.0,
+            mutation_rate: 0.0,
+        };
+        let mut count = 0;
+        for genome in genomes {
+            sum.attack += genome.attack;
+            sum.defense += genome.defense;
+            sum.aggression += genome.aggression;
+            sum.mutation_rate += genome.mutation_rate;
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        let count = count as f32;
+        Some(Self {
+            attack: sum.attack / count,
+            defense: sum.defense / count,
+            aggression: sum.aggression / count,
+            mutation_rate: sum.mutation_rate / count,
+        })
+    }
+}
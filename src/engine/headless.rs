@@ -0,0 +1,61 @@
+use crate::engine::export::{board_pixels, save_png, Capture};
+use crate::engine::game::Game;
+use crate::engine::palette::Palette;
+use crate::engine::render::{Command, Frontend};
+
+/// Shows nothing, for runs only watched through their statistics.
+pub struct Null {
+    /// Tick after which the game stops, if any.
+    pub until: Option<u64>,
+}
+
+impl Frontend for Null {
+    fn poll_commands(&mut self, game: &Game) -> Vec<Command> {
+        quit_after(game, self.until)
+    }
+
+    fn present(&mut self, _game: &Game, _tick_speed: f64, _paused: bool) {}
+}
+
+/// Writes the board as a numbered sequence of images, one pixel per cell, every `interval`
+/// ticks.
+pub struct ImageSequence {
+    /// Directory the images are written to.
+    pub directory: String,
+    /// How many ticks go by between two images.
+    pub interval: u64,
+    pub palette: Palette,
+}
+
+impl ImageSequence {
+    /// Creates the directory the images will be written to, if it doesn't exist yet.
+    pub fn new(directory: String, interval: u64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            interval,
+            palette: Palette::default(),
+        })
+    }
+}
+
+impl Capture for ImageSequence {
+    fn observe(&mut self, game: &Game) -> Option<String> {
+        if !game.tick_count.is_multiple_of(self.interval) {
+            return None;
+        }
+        let path = format!("{}/tick_{:08}.png", self.directory, game.tick_count);
+        let size = (game.board.width, game.board.height);
+        save_png(&board_pixels(game, &self.palette), size, 1, &path)
+            .err()
+            .map(|e| format!("Could not save {path}: {e}"))
+    }
+}
+
+/// Asks the game to quit once it went past the given tick.
+fn quit_after(game: &Game, until: Option<u64>) -> Vec<Command> {
+    match until {
+        Some(until) if game.tick_count >= until => vec![Command::Quit],
+        _ => Vec::new(),
+    }
+}
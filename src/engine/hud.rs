@@ -0,0 +1,130 @@
+use crate::engine::font;
+use crate::engine::game::Game;
+use crate::engine::stats::Stats;
+use sdl2::{
+    pixels::Color,
+    rect::Rect,
+    render::{BlendMode, WindowCanvas},
+};
+
+/// Size of a font pixel in the window.
+pub const SCALE: u32 = 2;
+/// Space between the panel's border and its text, in window pixels.
+const PADDING: u32 = 4;
+
+/// What the HUD shows: the tick, the speed, the seed, the rules and the population per type.
+pub fn lines(game: &Game, tick_speed: f64, measured_tps: f64, paused: bool) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "Tick {}{}",
+            game.tick_count,
+            if paused { " (paused)" } else { "" }
+        ),
+        format!(
+            "{measured_tps:.1} ticks/s (target {})",
+            speed_label(tick_speed)
+        ),
+        format!("Seed {}", game.seed),
+    ];
+    lines.extend(game.rules.summary());
+    for (cycle, phase) in game.phases() {
+        lines.push(format!("{cycle}: {phase}"));
+    }
+    lines.push(String::new());
+    for (t, count) in Stats::collect(game).ranking() {
+        lines.push(format!("{t:?} {count}"));
+    }
+    lines
+}
+
+/// The tick speed as shown to the user, "max" when the game runs as fast as possible.
+pub fn speed_label(tick_speed: f64) -> String {
+    if tick_speed.is_infinite() {
+        "max".to_string()
+    } else {
+        tick_speed.to_string()
+    }
+}
+
+/// Draws a list of color swatches followed by their label over a translucent black panel,
+/// with its top right corner at (x, y).
+///
+/// Returns the area of each entry, in order.
+pub fn draw_legend(
+    canvas: &mut WindowCanvas,
+    entries: &[(Color, String)],
+    (x, y): (i32, i32),
+) -> Vec<Rect> {
+    let line_height = (font::GLYPH_HEIGHT + font::SPACING) * SCALE;
+    let swatch = font::GLYPH_HEIGHT * SCALE;
+    let width = entries
+        .iter()
+        .map(|(_, label)| swatch + font::SPACING * SCALE * 2 + font::text_size(label, SCALE).0)
+        .max()
+        .unwrap_or(0);
+    let panel = Rect::new(
+        x - (width + 2 * PADDING) as i32,
+        y,
+        width + 2 * PADDING,
+        entries.len() as u32 * line_height + 2 * PADDING,
+    );
+
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(0, 0, 0, 180));
+    canvas.fill_rect(panel).unwrap();
+    canvas.set_blend_mode(BlendMode::None);
+    let mut rows: Vec<Rect> = Vec::new();
+    for (row, (color, label)) in entries.iter().enumerate() {
+        let left = panel.x() + PADDING as i32;
+        let top = panel.y() + (PADDING + row as u32 * line_height) as i32;
+        canvas.set_draw_color(*color);
+        canvas
+            .fill_rect(Rect::new(left, top, swatch, swatch))
+            .unwrap();
+        font::draw_text(
+            canvas,
+            label,
+            (left + (swatch + font::SPACING * SCALE * 2) as i32, top),
+            SCALE,
+            Color::WHITE,
+        );
+        rows.push(Rect::new(panel.x(), top, panel.width(), line_height));
+    }
+    rows
+}
+
+/// Draws lines of text over a translucent black panel, with its top left corner at (x, y).
+///
+/// Returns the panel's area.
+pub fn draw_panel(canvas: &mut WindowCanvas, lines: &[String], (x, y): (i32, i32)) -> Rect {
+    let line_height = (font::GLYPH_HEIGHT + font::SPACING) * SCALE;
+    let width = lines
+        .iter()
+        .map(|line| font::text_size(line, SCALE).0)
+        .max()
+        .unwrap_or(0);
+    let panel = Rect::new(
+        x,
+        y,
+        width + 2 * PADDING,
+        lines.len() as u32 * line_height + 2 * PADDING,
+    );
+
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(0, 0, 0, 180));
+    canvas.fill_rect(panel).unwrap();
+    canvas.set_blend_mode(BlendMode::None);
+    for (row, line) in lines.iter().enumerate() {
+        font::draw_text(
+            canvas,
+            line,
+            (
+                x + PADDING as i32,
+                y + (PADDING + row as u32 * line_height) as i32,
+            ),
+            SCALE,
+            Color::WHITE,
+        );
+    }
+    panel
+}
@@ -0,0 +1,65 @@
+use crate::engine::cell::Cell;
+use crate::engine::game::Game;
+
+/// Describes the cell at (x, y): a few lines about the cell first, then one line per neighbor.
+///
+/// Stats are the effective ones, with the genome and the current phase of every cycle applied.
+/// Neighbor lines show the damage of the move each side would rather use against the other,
+/// the best one when moves are picked at random, along with its type multiplier.
+pub fn inspect(game: &Game, (x, y): (usize, usize)) -> Vec<String> {
+    let cell = game.board.cells[x + y * game.board.width];
+    let mut summary = format!("({x}, {y}) {:?}", cell.cell_type);
+    if let Some(second_type) = cell.second_type {
+        summary += &format!("/{second_type:?}");
+    }
+    if let Some(tera_type) = cell.tera_type {
+        summary += &format!(" tera {tera_type:?}");
+    }
+    summary += &format!(
+        ", age {}, stage {}, {} conquests",
+        game.tick_count - cell.born,
+        cell.stage,
+        cell.conquests
+    );
+    let stats = format!(
+        "{:.1}/{:.1} HP, attack {:.2}",
+        cell.health,
+        cell.max_health,
+        cell.attack * cell.genome.map_or(1.0, |g| g.attack)
+    );
+    let mut moves = String::from("Moves:");
+    for &attack in cell.moves.iter().flatten() {
+        moves += &format!(" {attack:?}");
+        let modifier = game.cycle_modifier(attack);
+        if modifier != 1.0 {
+            moves += &format!(" x{modifier:.2}");
+        }
+    }
+
+    let mut lines = vec![summary, stats, moves];
+    if let Some(genome) = cell.genome {
+        lines.push(format!(
+            "Genome: attack {:.2} defense {:.2} aggression {:.2} mutation rate {:.2}",
+            genome.attack, genome.defense, genome.aggression, genome.mutation_rate
+        ));
+    }
+    for (n_x, n_y) in game.board.get_neighbors(x as i32, y as i32) {
+        let neighbor = game.board.cells[n_x + n_y * game.board.width];
+        let mut line = format!("({n_x}, {n_y}) {:?}: ", neighbor.cell_type);
+        if neighbor.cell_type == cell.cell_type {
+            line += "ally";
+        } else {
+            let dealt = Cell::preferred_move(game, cell, neighbor);
+            let taken = Cell::preferred_move(game, neighbor, cell);
+            line += &format!(
+                "deals {:.2} with {dealt:?} ({}x), takes {:.2} from {taken:?} ({}x)",
+                Cell::get_damage_with(game, dealt, cell, neighbor),
+                Cell::get_defense_multiplier(game, dealt, neighbor),
+                Cell::get_damage_with(game, taken, neighbor, cell),
+                Cell::get_defense_multiplier(game, taken, cell)
+            );
+        }
+        lines.push(line);
+    }
+    lines
+}
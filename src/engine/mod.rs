@@ -1,3 +1,18 @@
-mod cell;
+pub mod brush;
+pub mod camera;
+pub mod cell;
+pub mod cycle;
+pub mod evolution;
+pub mod export;
+pub mod font;
 pub mod game;
-mod render;
+pub mod genome;
+pub mod headless;
+pub mod hud;
+pub mod inspector;
+pub mod palette;
+pub mod render;
+pub mod rules;
+pub mod stats;
+pub mod tera;
+pub mod terminal;
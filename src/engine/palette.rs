@@ -0,0 +1,365 @@
+use crate::engine::cell::{Cell, Type};
+use sdl2::pixels::Color;
+use std::collections::HashMap;
+
+/// Colors of the original game.
+const DEFAULT: &str = "normal 168 167 122\n\
+    fire 238 129 48\n\
+    water 99 144 240\n\
+    grass 122 199 76\n\
+    electric 247 208 44\n\
+    ice 150 217 214\n\
+    fighting 194 46 40\n\
+    poison 163 62 161\n\
+    ground 226 191 101\n\
+    flying 169 143 243\n\
+    psychic 249 85 135\n\
+    bug 166 185 26\n\
+    rock 182 161 54\n\
+    ghost 115 87 151\n\
+    dark 112 87 70\n\
+    dragon 111 53 252\n\
+    steel 183 183 206\n\
+    fairy 214 133 173";
+
+/// Okabe and Ito's colors, along with darker and lighter shades of them to cover every type,
+/// picked so no two types look alike with any color vision deficiency.
+const OKABE_ITO: &str = "normal 188 188 188\n\
+    fire 230 159 0\n\
+    water 55 117 151\n\
+    grass 0 158 115\n\
+    electric 240 228 66\n\
+    ice 145 206 240\n\
+    fighting 85 37 0\n\
+    poison 204 121 167\n\
+    ground 149 103 0\n\
+    flying 89 163 204\n\
+    psychic 238 190 153\n\
+    bug 156 148 42\n\
+    rock 96 91 26\n\
+    ghost 132 78 108\n\
+    dark 61 61 61\n\
+    dragon 0 45 71\n\
+    steel 214 214 214\n\
+    fairy 249 244 179";
+
+/// Paul Tol's colors, picked from his schemes so no two types look alike with any color vision
+/// deficiency.
+const TOL: &str = "normal 238 238 187\n\
+    fire 204 51 17\n\
+    water 102 153 204\n\
+    grass 17 119 51\n\
+    electric 238 221 136\n\
+    ice 204 238 255\n\
+    fighting 187 85 102\n\
+    poison 170 51 119\n\
+    ground 204 187 68\n\
+    flying 187 204 238\n\
+    psychic 238 102 119\n\
+    bug 170 170 0\n\
+    rock 102 51 51\n\
+    ghost 34 34 85\n\
+    dark 34 85 85\n\
+    dragon 68 119 170\n\
+    steel 68 170 153\n\
+    fairy 255 170 187";
+
+/// Patterns drawn over big enough cells so types can be told apart without their color.
+///
+/// Each one is a 3x3 grid, one bit per square from the top left, in the order of `Type::TYPES`.
+const PATTERNS: [u16; 18] = [
+    0b000_000_000,
+    0b000_010_000,
+    0b000_111_000,
+    0b010_010_010,
+    0b010_111_010,
+    0b101_010_101,
+    0b100_010_001,
+    0b001_010_100,
+    0b101_000_101,
+    0b111_000_000,
+    0b000_000_111,
+    0b100_100_100,
+    0b001_001_001,
+    0b111_101_111,
+    0b010_101_010,
+    0b100_000_001,
+    0b111_010_010,
+    0b101_101_111,
+];
+
+/// Color of every type.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: HashMap<Type, Color>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::parse(DEFAULT).unwrap()
+    }
+}
+
+impl Palette {
+    /// Names of the built-in palettes, the colorblind-safe ones after the default one.
+    pub const NAMES: [&'static str; 3] = ["default", "okabe-ito", "tol"];
+
+    /// The built-in palette of the given name, if any.
+    pub fn named(name: &str) -> Option<Self> {
+        let text = match name {
+            "default" => DEFAULT,
+            "okabe-ito" => OKABE_ITO,
+            "tol" => TOL,
+            _ => return None,
+        };
+        Some(Self::parse(text).unwrap())
+    }
+
+    pub fn color(&self, t: Type) -> Color {
+        self.colors[&t]
+    }
+
+    /// Color of the cell's type, brightened a bit by each of its first three evolution stages,
+    /// not so much that the type can't be told, and darkened once terastallized.
+    pub fn cell_color(&self, cell: Cell) -> Color {
+        let color = brighten(self.color(cell.cell_type), 0.1 * cell.stage.min(3) as f32);
+        match cell.tera_type {
+            Some(_) => darken(color, 0.4),
+            None => color,
+        }
+    }
+
+    /// Every color `cell_color` gives, each type's at every evolution stage, terastallized or
+    /// not.
+    ///
+    /// Cells stop brightening after the third stage, so there are at most 144 of them.
+    pub fn cell_colors(&self) -> Vec<Color> {
+        let mut colors: Vec<Color> = Type::TYPES
+            .iter()
+            .flat_map(|&t| {
+                (0..=3).flat_map(move |stage| {
+                    [None, Some(t)].map(|tera_type| {
+                        self.cell_color(Cell {
+                            stage,
+                            tera_type,
+                            ..Cell::new(t)
+                        })
+                    })
+                })
+            })
+            .collect();
+        colors.sort_by_key(|c| (c.r, c.g, c.b));
+        colors.dedup();
+        colors
+    }
+
+    /// Parses a palette, made of one type per line followed by its red, green and blue:
+    /// ```text
+    /// # Comments start with a hash
+    /// base tol
+    /// fire 255 0 0
+    /// ```
+    /// An optional first line picks the built-in palette giving the types that aren't listed,
+    /// the default one otherwise.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut colors: HashMap<Type, Color> = HashMap::new();
+        let mut base = "default";
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some(&first) = words.first() else {
+                continue;
+            };
+            if first.starts_with('#') {
+                continue;
+            }
+
+            if first == "base" {
+                base = match words.get(1) {
+                    Some(&name) if Self::NAMES.contains(&name) => name,
+                    _ => return Err(format!("Line {number}: unknown base palette")),
+                };
+                continue;
+            }
+
+            let cell_type =
+                Type::from_name(first).ok_or(format!("Line {number}: unknown type {first}"))?;
+            let channels: Vec<u8> = words[1..]
+                .iter()
+                .filter_map(|channel| channel.parse::<u8>().ok())
+                .collect();
+            match channels[..] {
+                [r, g, b] if words.len() == 4 => colors.insert(cell_type, Color::RGB(r, g, b)),
+                _ => {
+                    return Err(format!(
+                        "Line {number}: {first} needs three numbers between 0 and 255"
+                    ))
+                }
+            };
+        }
+
+        // Types that aren't listed keep the base palette's colors
+        if colors.len() < Type::TYPES.len() {
+            let base = Self::named(base).unwrap();
+            for t in Type::TYPES {
+                colors.entry(t).or_insert(base.color(t));
+            }
+        }
+        Ok(Self { colors })
+    }
+
+    /// Squares of a 3x3 grid making up the type's pattern, as (column, row).
+    pub fn pattern(t: Type) -> impl Iterator<Item = (u32, u32)> {
+        let index = Type::TYPES.iter().position(|&other| other == t).unwrap();
+        let bits = PATTERNS[index];
+        (0..9)
+            .filter(move |square| bits & (1 << (8 - square)) != 0)
+            .map(|square| (square % 3, square / 3))
+    }
+}
+
+/// Moves a color towards black, `amount` going from 0 (unchanged) to 1 (black).
+pub fn darken(color: Color, amount: f32) -> Color {
+    let amount = amount.min(1.0);
+    let channel = |c: u8| (c as f32 * (1.0 - amount)) as u8;
+    Color::RGB(channel(color.r), channel(color.g), channel(color.b))
+}
+
+/// Moves a color towards white, `amount` going from 0 (unchanged) to 1 (white).
+pub fn brighten(color: Color, amount: f32) -> Color {
+    let amount = amount.min(1.0);
+    let channel = |c: u8| c + ((255 - c) as f32 * amount) as u8;
+    Color::RGB(channel(color.r), channel(color.g), channel(color.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Color vision deficiencies, as matrices simulating them in linear RGB: Viénot et al.'s for
+    /// protanopia and deuteranopia, Machado et al.'s for tritanopia.
+    const DEFICIENCIES: [(&str, [[f64; 3]; 3]); 3] = [
+        (
+            "protanopia",
+            [
+                [0.11238, 0.88762, 0.0],
+                [0.11238, 0.88762, 0.0],
+                [0.00401, -0.00401, 1.0],
+            ],
+        ),
+        (
+            "deuteranopia",
+            [
+                [0.29275, 0.70725, 0.0],
+                [0.29275, 0.70725, 0.0],
+                [-0.02234, 0.02234, 1.0],
+            ],
+        ),
+        (
+            "tritanopia",
+            [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.3039],
+            ],
+        ),
+    ];
+
+    /// How a color looks with the deficiency simulated by `matrix`.
+    fn simulate(color: Color, matrix: &[[f64; 3]; 3]) -> Color {
+        let linear = |c: u8| {
+            let c = c as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let encoded = |c: f64| {
+            let c = c.clamp(0.0, 1.0);
+            let c = if c <= 0.0031308 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c * 255.0).round() as u8
+        };
+        let rgb = [linear(color.r), linear(color.g), linear(color.b)];
+        let [r, g, b] = matrix.map(|row| encoded(row.iter().zip(rgb).map(|(m, c)| m * c).sum()));
+        Color::RGB(r, g, b)
+    }
+
+    /// Rough perceived distance between two colors, weighting channels by how sensitive the
+    /// eye is to them ("redmean").
+    fn distance(a: Color, b: Color) -> f64 {
+        let red_mean = (a.r as f64 + b.r as f64) / 2.0;
+        let (dr, dg, db) = (
+            a.r as f64 - b.r as f64,
+            a.g as f64 - b.g as f64,
+            a.b as f64 - b.b as f64,
+        );
+        ((2.0 + red_mean / 256.0) * dr * dr
+            + 4.0 * dg * dg
+            + (2.0 + (255.0 - red_mean) / 256.0) * db * db)
+            .sqrt()
+    }
+
+    /// Panics if two types of the palette are closer than `min_distance`, once both are seen
+    /// through `see`.
+    fn assert_apart(name: &str, vision: &str, min_distance: f64, see: impl Fn(Color) -> Color) {
+        let palette = Palette::named(name).unwrap();
+        for (i, &a) in Type::TYPES.iter().enumerate() {
+            for &b in &Type::TYPES[i + 1..] {
+                let d = distance(see(palette.color(a)), see(palette.color(b)));
+                assert!(
+                    d >= min_distance,
+                    "{a:?} and {b:?} look alike in {name} with {vision} ({d:.0})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn built_in_palettes_keep_types_apart() {
+        for name in Palette::NAMES {
+            assert_apart(name, "normal vision", 60.0, |color| color);
+        }
+    }
+
+    #[test]
+    fn colorblind_safe_palettes_keep_types_apart() {
+        for name in &Palette::NAMES[1..] {
+            for (deficiency, matrix) in &DEFICIENCIES {
+                assert_apart(name, deficiency, 45.0, |color| simulate(color, matrix));
+            }
+        }
+    }
+
+    #[test]
+    fn evolved_cells_keep_their_type_color() {
+        let palette = Palette::default();
+        for t in Type::TYPES {
+            let evolved = palette.cell_color(Cell {
+                stage: 10,
+                ..Cell::new(t)
+            });
+            assert_ne!(evolved, Color::WHITE);
+            assert_eq!(evolved, brighten(palette.color(t), 0.3));
+        }
+    }
+
+    #[test]
+    fn terastallized_cells_look_darker() {
+        let palette = Palette::default();
+        let cell = Cell::new(Type::Fire);
+        let tera = Cell {
+            tera_type: Some(Type::Water),
+            ..cell
+        };
+        assert_eq!(
+            palette.cell_color(tera),
+            darken(palette.cell_color(cell), 0.4)
+        );
+        assert!(palette.cell_colors().contains(&palette.cell_color(tera)));
+    }
+}
@@ -1,94 +1,652 @@
+use crate::engine::brush::Brush;
+use crate::engine::camera::Camera;
+use crate::engine::export::save_png;
+use crate::engine::palette::{brighten, darken, Palette};
+use crate::engine::stats::Stats;
 use crate::engine::{cell::Type, game::Game};
-use sdl2::{event::Event, keyboard::Keycode, pixels::Color, render::WindowCanvas, EventPump};
+use crate::engine::{hud, inspector};
+use sdl2::{
+    event::{Event, WindowEvent},
+    keyboard::{Keycode, Mod},
+    mouse::MouseButton,
+    pixels::{Color, PixelFormatEnum},
+    rect::Rect,
+    render::{Texture, WindowCanvas},
+    EventPump,
+};
+use std::time::Instant;
+
+const TITLE: &str = "Pokemon's Game of Life";
+/// Size in pixels from which cells are big enough to show details, like their tera type.
+const MIN_DETAILED_CELL_SIZE: f64 = 3.0;
+/// Size in pixels from which cells are big enough to show their type's pattern.
+const MIN_PATTERN_CELL_SIZE: u32 = 6;
+/// Keys selecting the first ten types, in the keyboard's order.
+const NUMBER_KEYS: [Keycode; 10] = [
+    Keycode::Num1,
+    Keycode::Num2,
+    Keycode::Num3,
+    Keycode::Num4,
+    Keycode::Num5,
+    Keycode::Num6,
+    Keycode::Num7,
+    Keycode::Num8,
+    Keycode::Num9,
+    Keycode::Num0,
+];
+/// Either shift key, held to pan with the arrows.
+const SHIFT: Mod = Mod::LSHIFTMOD.union(Mod::RSHIFTMOD);
+
+/// What the user asked for through the window.
+pub enum Command {
+    Quit,
+    TogglePause,
+    /// Runs a single tick, pausing the game.
+    Step,
+    SpeedUp,
+    SlowDown,
+    /// Replaces the given cells with new ones of the given type.
+    Paint(Vec<(usize, usize)>, Type),
+}
+
+/// What the color of a cell shows.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RenderMode {
+    /// The cell's type.
+    Type,
+    /// The cell's type, darker the more damaged the cell is.
+    Health,
+    /// Heatmap of the ticks since the cell appeared, relative to the oldest cell.
+    Age,
+    /// Heatmap of how many times each location was conquered, relative to the most conquered.
+    Conquests,
+    /// The cell's type, only for cells next to a different type.
+    Front,
+}
+
+impl RenderMode {
+    /// The mode after this one, looping around.
+    fn next(self) -> Self {
+        match self {
+            Self::Type => Self::Health,
+            Self::Health => Self::Age,
+            Self::Age => Self::Conquests,
+            Self::Conquests => Self::Front,
+            Self::Front => Self::Type,
+        }
+    }
+
+    /// Whether cells can change color without being dirty, so every cell must be redrawn.
+    fn redraws_everything(self) -> bool {
+        matches!(self, Self::Age | Self::Conquests | Self::Front)
+    }
+
+    /// Whether the colors are the types' ones, which the patterns stand in for.
+    fn shows_types(self) -> bool {
+        matches!(self, Self::Type | Self::Health | Self::Front)
+    }
+}
+
+/// Where the game is shown, chosen at startup.
+pub trait Frontend {
+    /// Turns pending input into commands for the game loop, `Command::Quit` to stop the game.
+    fn poll_commands(&mut self, game: &Game) -> Vec<Command>;
+
+    /// Shows the game, whose board marks the cells changed since the last call as dirty.
+    fn present(&mut self, game: &Game, tick_speed: f64, paused: bool);
+
+    /// Whether presenting already waits for the display to refresh.
+    fn vsync(&self) -> bool {
+        false
+    }
+
+    /// Whether the game is drawn on standard output, so nothing else may be printed there.
+    fn uses_stdout(&self) -> bool {
+        false
+    }
+}
 
 pub struct Renderer {
     canvas: WindowCanvas,
     pub events: EventPump,
+    pub brush: Brush,
+    pub camera: Camera,
+    /// The board, one pixel per cell, scaled to the window when drawn.
+    texture: Texture,
+    /// Content of the texture, kept to only update the dirty cells.
+    pixels: Vec<u8>,
+    /// Whether every cell must be drawn on the next frame, not only the dirty ones.
+    full_redraw: bool,
+    /// Whether the board keeps its aspect ratio, leaving black bars around it.
+    pub letterbox: bool,
+    /// Whether the cell under the mouse is described in the window.
+    inspecting: bool,
+    /// Cell under the mouse, if any.
+    hovered: Option<(usize, usize)>,
+    /// Whether the HUD is drawn over the board.
+    show_hud: bool,
+    /// When and at which tick the speed was last measured.
+    speed_sample: (Instant, u64),
+    /// Actual speed of the game, in ticks per second.
+    measured_tps: f64,
+    /// Whether the legend of the types' colors is drawn over the board.
+    show_legend: bool,
+    /// Where each type's entry was last drawn in the legend, to find out which one is clicked.
+    legend_rows: Vec<(Type, Rect)>,
+    /// Type picked in the legend: cells of other types are dimmed.
+    highlight: Option<Type>,
+    pub mode: RenderMode,
+    /// Whether the board's image must be saved on the next frame.
+    export_requested: bool,
+    /// Size of a cell in saved images, in pixels.
+    pub screenshot_scale: usize,
+    pub palette: Palette,
+    /// Whether big enough cells show their type's pattern, to tell types apart without colors.
+    pub patterns: bool,
+    /// Whether presenting a frame waits for the display to refresh.
+    pub vsync: bool,
+}
+
+/// Largest values shown by the heatmaps, which are relative to them.
+///
+/// Only the one shown by the current mode is looked up, the other one is left at 1.
+struct HeatRange {
+    max_age: u64,
+    max_conquests: u32,
+}
+
+impl HeatRange {
+    fn of(game: &Game, mode: RenderMode) -> Self {
+        let mut range = Self {
+            max_age: 1,
+            max_conquests: 1,
+        };
+        match mode {
+            RenderMode::Age => {
+                let oldest = game
+                    .board
+                    .cells
+                    .iter()
+                    .map(|cell| cell.born)
+                    .min()
+                    .unwrap_or(0);
+                range.max_age = (game.tick_count - oldest).max(1);
+            }
+            RenderMode::Conquests => {
+                let most = game.board.conquest_counts.iter().copied().max();
+                range.max_conquests = most.unwrap_or(0).max(1);
+            }
+            _ => {}
+        }
+        range
+    }
+}
+
+/// Why the window could not be opened.
+#[derive(Debug)]
+pub enum SetupError {
+    /// There is no video device to open a window on, like on a machine without a display.
+    NoDisplay(String),
+    /// SDL failed at anything else.
+    Sdl(String),
+}
+
+impl std::fmt::Display for SetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoDisplay(e) => write!(f, "no display available ({e})"),
+            Self::Sdl(e) => write!(f, "{e}"),
+        }
+    }
 }
 
 impl Renderer {
-    pub fn setup((w, h): (u32, u32)) -> Renderer {
-        let sdl_context = sdl2::init().unwrap();
-        let video_subsystem = sdl_context.video().unwrap();
-        let window = video_subsystem.window("Pokemon's Game of Life", w, h);
+    pub fn setup(
+        (w, h): (u32, u32),
+        (width, height): (usize, usize),
+        vsync: bool,
+    ) -> Result<Renderer, SetupError> {
+        let sdl_context = sdl2::init().map_err(SetupError::Sdl)?;
+        let video_subsystem = sdl_context.video().map_err(SetupError::NoDisplay)?;
+        let mut window = video_subsystem.window(TITLE, w, h);
+        window.resizable();
 
-        // If something panics, it's most likely there
-        let mut canvas = window.build().unwrap().into_canvas().build().unwrap();
+        let sdl_error = |e: &dyn std::fmt::Display| SetupError::Sdl(e.to_string());
+        let window = window.build().map_err(|e| sdl_error(&e))?;
+        let mut canvas = window.into_canvas();
+        if vsync {
+            canvas = canvas.present_vsync();
+        }
+        let mut canvas = canvas.build().map_err(|e| sdl_error(&e))?;
         canvas.set_draw_color(Color::BLACK);
+        // Cells stay sharp squares when scaled up
+        sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "nearest");
+        let texture = canvas
+            .create_texture_streaming(PixelFormatEnum::RGB24, width as u32, height as u32)
+            .map_err(|e| sdl_error(&e))?;
 
-        let event_pump = sdl_context.event_pump().unwrap();
+        let event_pump = sdl_context.event_pump().map_err(SetupError::Sdl)?;
 
-        Self {
+        Ok(Self {
             canvas,
             events: event_pump,
+            brush: Brush::new(),
+            camera: Camera::new(),
+            texture,
+            pixels: vec![0; width * height * 3],
+            full_redraw: true,
+            letterbox: false,
+            inspecting: false,
+            hovered: None,
+            show_hud: false,
+            speed_sample: (Instant::now(), 0),
+            measured_tps: 0.0,
+            show_legend: false,
+            legend_rows: Vec::new(),
+            highlight: None,
+            mode: RenderMode::Type,
+            export_requested: false,
+            screenshot_scale: 1,
+            palette: Palette::default(),
+            patterns: false,
+            vsync,
+        })
+    }
+
+    /// Updates the board's image for the dirty cells, then scales it to the window.
+    pub fn render_optimized(&mut self, game: &Game, tick_speed: f64, paused: bool) {
+        self.update_title(game, tick_speed, paused);
+
+        // One pixel per cell, three bytes per pixel
+        let full_redraw = self.full_redraw || self.mode.redraws_everything();
+        self.full_redraw = false;
+        let heat_range = HeatRange::of(game, self.mode);
+        for index in 0..game.board.cells.len() {
+            if full_redraw || game.board.dirty[index] {
+                let color = self.cell_color(game, index, &heat_range);
+                self.pixels[index * 3..index * 3 + 3].copy_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+        if self.export_requested {
+            self.export_requested = false;
+            self.export_image(game);
+        }
+        self.texture
+            .update(None, &self.pixels, game.board.width * 3)
+            .unwrap();
+
+        self.canvas.set_draw_color(Color::BLACK);
+        self.canvas.clear();
+        let viewport = self.viewport(&game.board);
+        let view = viewport.size();
+        let board = (game.board.width, game.board.height);
+        self.canvas.set_clip_rect(viewport);
+        for (src, mut dst) in self.camera.copy_regions(view, board) {
+            dst.offset(viewport.x(), viewport.y());
+            self.canvas.copy(&self.texture, src, dst).unwrap();
+        }
+
+        // Details that don't fit in a pixel are drawn over big enough cells
+        let (w_cell, h_cell) = self.camera.cell_size(view, board);
+        if w_cell >= MIN_DETAILED_CELL_SIZE && h_cell >= MIN_DETAILED_CELL_SIZE {
+            for ((x, y), mut rect) in self.camera.visible_cells(view, board) {
+                rect.offset(viewport.x(), viewport.y());
+                self.draw_cell_details(game, x + y * game.board.width, rect);
+            }
+        }
+        self.canvas.set_clip_rect(None);
+
+        let (sample_time, sample_tick) = self.speed_sample;
+        let elapsed = sample_time.elapsed().as_secs_f64();
+        if elapsed >= 1.0 {
+            self.measured_tps = (game.tick_count - sample_tick) as f64 / elapsed;
+            self.speed_sample = (Instant::now(), game.tick_count);
+        }
+        // The inspected cell is described under the HUD, or in its place when it's hidden
+        let mut inspector_top = 0;
+        if self.show_hud {
+            let lines = hud::lines(game, tick_speed, self.measured_tps, paused);
+            inspector_top = hud::draw_panel(&mut self.canvas, &lines, (0, 0)).bottom();
+        }
+        if let (true, Some(hovered)) = (self.inspecting, self.hovered) {
+            let lines = inspector::inspect(game, hovered);
+            hud::draw_panel(&mut self.canvas, &lines, (0, inspector_top));
+        }
+        if self.show_legend {
+            self.draw_legend(game);
         }
+        self.canvas.present();
     }
 
-    // Only renders the changed cells
-    pub fn render_optimized(game: &mut Game) -> bool {
-        for event in game.renderer.events.poll_iter() {
+    /// Part of the window the board is drawn in.
+    ///
+    /// With letterboxing, it keeps the board's aspect ratio and is centered in the window.
+    /// Otherwise, it is the whole window.
+    fn viewport(&self, board: &crate::engine::game::Board) -> Rect {
+        let (w_win, h_win) = self.canvas.output_size().unwrap();
+        if !self.letterbox {
+            return Rect::new(0, 0, w_win, h_win);
+        }
+        let scale = (w_win as f64 / board.width as f64).min(h_win as f64 / board.height as f64);
+        let (w_view, h_view) = (
+            ((board.width as f64 * scale) as u32).max(1),
+            ((board.height as f64 * scale) as u32).max(1),
+        );
+        Rect::new(
+            (w_win.saturating_sub(w_view) / 2) as i32,
+            (h_win.saturating_sub(h_view) / 2) as i32,
+            w_view,
+            h_view,
+        )
+    }
+
+    /// Pans the view a tenth of its size in the given direction.
+    fn pan_view(&mut self, game: &Game, (dx, dy): (i32, i32)) {
+        let board = (game.board.width, game.board.height);
+        let step = |cells: usize| cells as f64 / self.camera.zoom / 10.0;
+        let (x_step, y_step) = (step(board.0), step(board.1));
+        self.camera
+            .pan((dx as f64 * x_step, dy as f64 * y_step), board);
+    }
+
+    /// The cell drawn at the given window position, if any.
+    fn cell_under(&self, game: &Game, (x, y): (i32, i32)) -> Option<(usize, usize)> {
+        let viewport = self.viewport(&game.board);
+        if !viewport.contains_point((x, y)) {
+            return None;
+        }
+        Some(self.camera.cell_at(
+            (x - viewport.x(), y - viewport.y()),
+            viewport.size(),
+            (game.board.width, game.board.height),
+        ))
+    }
+
+    /// Paints with the brush around the cell under the given window position.
+    fn paint_at(&self, game: &Game, position: (i32, i32)) -> Option<Command> {
+        if self
+            .legend_rows
+            .iter()
+            .any(|(_, row)| row.contains_point(position))
+        {
+            return None;
+        }
+        let cell = self.cell_under(game, position)?;
+        let brush = &self.brush;
+        Some(Command::Paint(
+            brush.cells(cell, (game.board.width, game.board.height)),
+            brush.cell_type,
+        ))
+    }
+
+    /// Shows the speed, the render mode, whether the game is paused, the current phase of every
+    /// cycle and the brush in the window title.
+    fn update_title(&mut self, game: &Game, tick_speed: f64, paused: bool) {
+        let mut title = format!(
+            "{TITLE} | {} ticks/s | {:?} view",
+            hud::speed_label(tick_speed),
+            self.mode
+        );
+        if paused {
+            title += " [paused]";
+        }
+        for (cycle, phase) in game.phases() {
+            title += &format!(" | {cycle}: {phase}");
+        }
+        let brush = &self.brush;
+        title += &format!(
+            " | Brush: {:?} {} {:?}",
+            brush.cell_type, brush.size, brush.shape
+        );
+        if self.canvas.window().title() != title {
+            self.canvas.window_mut().set_title(&title).unwrap();
+        }
+    }
+
+    /// Color of the cell at `index` on the board's image.
+    fn cell_color(&self, game: &Game, index: usize, heat_range: &HeatRange) -> Color {
+        let cell = game.board.cells[index];
+        let mut color = self.palette.cell_color(cell);
+        match self.mode {
+            RenderMode::Type => {}
+            RenderMode::Health => {
+                // Never fully black, so the type stays recognizable
+                color = darken(
+                    color,
+                    0.8 * (1.0 - cell.health / cell.max_health).clamp(0.0, 1.0),
+                );
+            }
+            RenderMode::Age => {
+                color = heat_color((game.tick_count - cell.born) as f32 / heat_range.max_age as f32)
+            }
+            RenderMode::Conquests => {
+                // Logarithmic, as a few hot spots would otherwise hide everything else
+                let count = game.board.conquest_counts[index] as f32;
+                color = heat_color(count.ln_1p() / (heat_range.max_conquests as f32).ln_1p());
+            }
+            RenderMode::Front => {
+                if !game
+                    .board
+                    .is_front(index % game.board.width, index / game.board.width)
+                {
+                    color = darken(color, 0.85);
+                }
+            }
+        }
+        match self.highlight {
+            Some(t) if t != cell.cell_type => darken(color, 0.75),
+            _ => color,
+        }
+    }
+
+    /// Saves the board's image as the current render mode shows it, scaled up
+    /// `screenshot_scale` times.
+    fn export_image(&mut self, game: &Game) {
+        let path = format!(
+            "{}_{}.png",
+            format!("{:?}", self.mode).to_lowercase(),
+            game.tick_count
+        );
+        let size = (game.board.width, game.board.height);
+        match save_png(&self.pixels, size, self.screenshot_scale, &path) {
+            Ok(()) => println!("Saved {path}"),
+            Err(e) => println!("Could not save {path}: {e}"),
+        }
+    }
+
+    /// Lists every type alive with its color and population share, most populated first.
+    fn draw_legend(&mut self, game: &Game) {
+        let total = game.board.cells.len() as f64;
+        let ranking = Stats::collect(game).ranking();
+        let entries: Vec<(Color, String)> = ranking
+            .iter()
+            .map(|&(t, count)| {
+                let marker = if self.highlight == Some(t) { ">" } else { " " };
+                (
+                    self.palette.color(t),
+                    format!("{marker}{t:?} {:.1}%", count as f64 * 100.0 / total),
+                )
+            })
+            .collect();
+        let (w_win, _) = self.canvas.output_size().unwrap();
+        let rows = hud::draw_legend(&mut self.canvas, &entries, (w_win as i32, 0));
+        self.legend_rows = ranking.iter().map(|&(t, _)| t).zip(rows).collect();
+    }
+
+    fn draw_cell_details(&mut self, game: &Game, index: usize, rect: Rect) {
+        let cell = game.board.cells[index];
+        // The pattern is drawn in a shade of the cell's color, lighter on dark cells
+        if self.patterns
+            && self.mode.shows_types()
+            && rect.width().min(rect.height()) >= MIN_PATTERN_CELL_SIZE
+        {
+            let [r, g, b] = self.pixels[index * 3..index * 3 + 3] else {
+                unreachable!("pixels are three bytes long")
+            };
+            let color = Color::RGB(r, g, b);
+            let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+            self.canvas.set_draw_color(if luminance > 128.0 {
+                darken(color, 0.5)
+            } else {
+                brighten(color, 0.5)
+            });
+            let (w_square, h_square) = (rect.width() / 3, rect.height() / 3);
+            for (column, row) in Palette::pattern(cell.cell_type) {
+                let square = Rect::new(
+                    rect.x() + (column * w_square) as i32,
+                    rect.y() + (row * h_square) as i32,
+                    w_square,
+                    h_square,
+                );
+                self.canvas.fill_rect(square).unwrap();
+            }
+        }
+
+        // Terastallized cells show their tera type as a smaller square in their middle
+        if let Some(tera_type) = cell.tera_type {
+            let mut inner = rect;
+            inner.resize((rect.width() / 2).max(1), (rect.height() / 2).max(1));
+            inner.center_on(rect.center());
+            self.canvas.set_draw_color(self.palette.color(tera_type));
+            self.canvas.fill_rect(inner).unwrap();
+        }
+    }
+}
+
+impl Frontend for Renderer {
+    /// Turns pending window events into commands for the game loop.
+    fn poll_commands(&mut self, game: &Game) -> Vec<Command> {
+        let mut commands: Vec<Command> = Vec::new();
+        let events: Vec<Event> = self.events.poll_iter().collect();
+        for event in events {
+            if let Event::MouseMotion { x, y, .. } | Event::MouseButtonDown { x, y, .. } = event {
+                self.hovered = self.cell_under(game, (x, y));
+            }
             match event {
                 Event::Quit { .. }
                 | Event::KeyDown {
                     keycode: Some(Keycode::Escape),
                     ..
+                } => commands.push(Command::Quit),
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    keymod,
+                    ..
+                } => match keycode {
+                    // Shift and the arrows pan, the arrows alone step and change the speed
+                    Keycode::Left if keymod.intersects(SHIFT) => self.pan_view(game, (-1, 0)),
+                    Keycode::Right if keymod.intersects(SHIFT) => self.pan_view(game, (1, 0)),
+                    Keycode::Up if keymod.intersects(SHIFT) => self.pan_view(game, (0, -1)),
+                    Keycode::Down if keymod.intersects(SHIFT) => self.pan_view(game, (0, 1)),
+                    Keycode::Space | Keycode::P => commands.push(Command::TogglePause),
+                    Keycode::N | Keycode::Right => commands.push(Command::Step),
+                    Keycode::Equals | Keycode::Plus | Keycode::KpPlus | Keycode::Up => {
+                        commands.push(Command::SpeedUp)
+                    }
+                    Keycode::Minus | Keycode::KpMinus | Keycode::Down => {
+                        commands.push(Command::SlowDown)
+                    }
+                    Keycode::Home => self.camera = Camera::new(),
+                    Keycode::A => self.letterbox = !self.letterbox,
+                    Keycode::RightBracket => self.brush.cycle_type(true),
+                    Keycode::LeftBracket => self.brush.cycle_type(false),
+                    Keycode::Period => self.brush.size = (self.brush.size + 1).min(Brush::MAX_SIZE),
+                    Keycode::Comma => self.brush.size = (self.brush.size - 1).max(1),
+                    Keycode::B => self.brush.toggle_shape(),
+                    Keycode::I => self.inspecting = !self.inspecting,
+                    Keycode::H => self.show_hud = !self.show_hud,
+                    Keycode::M => {
+                        self.mode = self.mode.next();
+                        self.full_redraw = true;
+                    }
+                    Keycode::E => self.export_requested = true,
+                    Keycode::T => self.patterns = !self.patterns,
+                    Keycode::L => {
+                        self.show_legend = !self.show_legend;
+                        self.legend_rows.clear();
+                    }
+                    _ => {
+                        // Number keys select one of the first ten types
+                        if let Some(index) = NUMBER_KEYS.iter().position(|&k| k == keycode) {
+                            self.brush.cell_type = Type::TYPES[index];
+                        }
+                    }
+                },
+                Event::MouseButtonDown {
+                    mouse_btn: MouseButton::Left,
+                    x,
+                    y,
+                    ..
                 } => {
-                    return true;
+                    let clicked = self
+                        .legend_rows
+                        .iter()
+                        .find(|(_, row)| row.contains_point((x, y)))
+                        .map(|&(t, _)| t);
+                    match clicked {
+                        // Clicking the highlighted type again shows every type
+                        Some(t) => {
+                            self.highlight = if self.highlight == Some(t) {
+                                None
+                            } else {
+                                Some(t)
+                            };
+                            self.full_redraw = true;
+                        }
+                        None => commands.extend(self.paint_at(game, (x, y))),
+                    }
+                }
+                Event::MouseMotion {
+                    mousestate, x, y, ..
+                } if mousestate.left() => commands.extend(self.paint_at(game, (x, y))),
+                Event::MouseMotion {
+                    mousestate,
+                    xrel,
+                    yrel,
+                    ..
+                } if mousestate.right() || mousestate.middle() => {
+                    // Dragging moves the board along with the mouse
+                    let view = self.viewport(&game.board).size();
+                    let board = (game.board.width, game.board.height);
+                    let (w_cell, h_cell) = self.camera.cell_size(view, board);
+                    self.camera
+                        .pan((-xrel as f64 / w_cell, -yrel as f64 / h_cell), board);
                 }
+                Event::MouseWheel { y, .. } => {
+                    let mouse = self.events.mouse_state();
+                    let viewport = self.viewport(&game.board);
+                    let anchor = (mouse.x() - viewport.x(), mouse.y() - viewport.y());
+                    let board = (game.board.width, game.board.height);
+                    self.camera.zoom_at(y, anchor, viewport.size(), board);
+                }
+                // The window's content may have been lost
+                Event::Window {
+                    win_event:
+                        WindowEvent::Resized(..)
+                        | WindowEvent::SizeChanged(..)
+                        | WindowEvent::Exposed
+                        | WindowEvent::Restored
+                        | WindowEvent::Maximized,
+                    ..
+                } => self.full_redraw = true,
                 _ => {}
             };
         }
+        commands
+    }
 
-        game.renderer.canvas.present();
-        for x in 0..game.board.width {
-            for y in 0..game.board.height {
-                let cell = &game.board.cells[x + y * game.board.width];
-                if cell.changed {
-                    Self::draw_cell(*cell, game, (x, y));
-                }
-            }
-        }
-        game.renderer.canvas.present();
-        false
+    fn present(&mut self, game: &Game, tick_speed: f64, paused: bool) {
+        self.render_optimized(game, tick_speed, paused);
     }
 
-    fn draw_cell(cell: crate::engine::cell::Cell, game: &mut Game, (x, y): (usize, usize)) {
-        game.renderer
-            .canvas
-            .set_draw_color(get_color(cell.cell_type));
-        game.renderer
-            .canvas
-            .fill_rect(sdl2::rect::Rect::new(
-                (x * game.renderer.canvas.output_size().unwrap().0 as usize / game.board.width)
-                    as i32,
-                (y * game.renderer.canvas.output_size().unwrap().1 as usize / game.board.height)
-                    as i32,
-                (game.renderer.canvas.output_size().unwrap().0 as usize / game.board.width) as u32,
-                (game.renderer.canvas.output_size().unwrap().1 as usize / game.board.height) as u32,
-            ))
-            .unwrap();
+    fn vsync(&self) -> bool {
+        self.vsync
     }
 }
 
-fn get_color(t: Type) -> Color {
-    match t {
-        Type::Normal => Color::RGB(168, 167, 122),
-        Type::Fire => Color::RGB(238, 129, 48),
-        Type::Water => Color::RGB(99, 144, 240),
-        Type::Grass => Color::RGB(122, 199, 76),
-        Type::Electric => Color::RGB(247, 208, 44),
-        Type::Ice => Color::RGB(150, 217, 214),
-        Type::Fighting => Color::RGB(194, 46, 40),
-        Type::Poison => Color::RGB(163, 62, 161),
-        Type::Ground => Color::RGB(226, 191, 101),
-        Type::Flying => Color::RGB(169, 143, 243),
-        Type::Psychic => Color::RGB(249, 85, 135),
-        Type::Bug => Color::RGB(166, 185, 26),
-        Type::Rock => Color::RGB(182, 161, 54),
-        Type::Ghost => Color::RGB(115, 87, 151),
-        Type::Dragon => Color::RGB(111, 53, 252),
-        Type::Dark => Color::RGB(112, 87, 70),
-        Type::Steel => Color::RGB(183, 183, 206),
-        Type::Fairy => Color::RGB(214, 133, 173),
-    }
+/// Color of a heatmap, going from black (0) to red, yellow, then white (1).
+fn heat_color(heat: f32) -> Color {
+    let heat = heat.clamp(0.0, 1.0) * 3.0;
+    let channel = |from: f32| ((heat - from).clamp(0.0, 1.0) * 255.0) as u8;
+    Color::RGB(channel(0.0), channel(1.0), channel(2.0))
 }
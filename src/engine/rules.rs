@@ -0,0 +1,107 @@
+use crate::engine::cycle::Cycle;
+use crate::engine::evolution::Evolution;
+use crate::engine::tera::Tera;
+
+/// Optional rules changing how the simulation plays out.
+///
+/// The default rules behave like the original game: every cell attacks with its own type.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// How many moves a cell knows, its own type included (between 1 and `MAX_MOVES`).
+    pub moveset_size: usize,
+    /// How a cell picks which of its moves to use against a neighbor.
+    pub move_policy: MovePolicy,
+    /// Whether and how cells evolve after conquering others.
+    pub evolution: Option<Evolution>,
+    /// Whether cells carry a genome inherited, with mutations, by the cells they conquer.
+    pub genomes: bool,
+    /// Whether and when cells may terastallize.
+    pub tera: Option<Tera>,
+    /// Inverse Battle: the type chart is inverted.
+    pub inverse: bool,
+    /// Cycles making some types stronger depending on the tick, none if empty.
+    pub cycles: Vec<Cycle>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            moveset_size: 1,
+            move_policy: MovePolicy::Best,
+            evolution: None,
+            genomes: false,
+            tera: None,
+            inverse: false,
+            cycles: Vec::new(),
+        }
+    }
+}
+
+impl Rules {
+    /// One line per rule, to show which ones a run uses.
+    pub fn summary(&self) -> Vec<String> {
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        vec![
+            format!("Moves: {} ({:?})", self.moveset_size, self.move_policy),
+            format!(
+                "Evolution: {}",
+                match &self.evolution {
+                    Some(evolution) => format!("{} stages", evolution.stages.len()),
+                    None => "off".to_string(),
+                }
+            ),
+            format!("Genomes: {}", on_off(self.genomes)),
+            format!(
+                "Tera: {}",
+                match &self.tera {
+                    Some(tera) => format!(
+                        "{} chance, under {} health{}",
+                        tera.chance,
+                        tera.low_health,
+                        match tera.target {
+                            Some(target) => format!(", to {target:?}"),
+                            None => String::new(),
+                        }
+                    ),
+                    None => "off".to_string(),
+                }
+            ),
+            format!("Inverse: {}", on_off(self.inverse)),
+            format!(
+                "Cycles: {}",
+                if self.cycles.is_empty() {
+                    "off".to_string()
+                } else {
+                    self.cycles
+                        .iter()
+                        .map(|cycle| cycle.name.clone())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                }
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MovePolicy {
+    /// The move with the best multiplier against the neighbor.
+    Best,
+    /// Any known move, picked at random.
+    Random,
+    /// Always the first move, which is the cell's own type.
+    First,
+}
+
+impl std::str::FromStr for MovePolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "best" => Ok(Self::Best),
+            "random" => Ok(Self::Random),
+            "first" => Ok(Self::First),
+            _ => Err(()),
+        }
+    }
+}
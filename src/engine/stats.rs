@@ -0,0 +1,82 @@
+use crate::engine::cell::Type;
+use crate::engine::game::Game;
+use crate::engine::genome::Genome;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Snapshot of the board's population at a given tick.
+pub struct Stats {
+    pub tick: u64,
+    pub population: HashMap<Type, usize>,
+    /// Cells that evolved at least once, per type.
+    pub evolved: HashMap<Type, usize>,
+    /// Terastallized cells, per original type.
+    pub terastallized: HashMap<Type, usize>,
+    /// Average genome of the board, if cells carry one.
+    pub genome: Option<Genome>,
+    /// Current phase of every cycle, along with the cycle's name.
+    pub phases: Vec<(String, String)>,
+}
+
+impl Stats {
+    pub fn collect(game: &Game) -> Self {
+        let mut population: HashMap<Type, usize> = HashMap::new();
+        let mut evolved: HashMap<Type, usize> = HashMap::new();
+        let mut terastallized: HashMap<Type, usize> = HashMap::new();
+        for cell in game.board.cells.iter() {
+            *population.entry(cell.cell_type).or_insert(0) += 1;
+            if cell.stage > 0 {
+                *evolved.entry(cell.cell_type).or_insert(0) += 1;
+            }
+            if cell.tera_type.is_some() {
+                *terastallized.entry(cell.cell_type).or_insert(0) += 1;
+            }
+        }
+        Self {
+            tick: game.tick_count,
+            population,
+            evolved,
+            terastallized,
+            phases: game.phases(),
+            genome: Genome::average(game.board.cells.iter().filter_map(|c| c.genome.as_ref())),
+        }
+    }
+
+    /// Types still alive, most populated first.
+    pub fn ranking(&self) -> Vec<(Type, usize)> {
+        let mut ranking: Vec<(Type, usize)> = self
+            .population
+            .iter()
+            .map(|(&t, &count)| (t, count))
+            .collect();
+        ranking.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        ranking
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tick {}:", self.tick)?;
+        for (cycle, phase) in self.phases.iter() {
+            write!(f, " [{cycle}: {phase}]")?;
+        }
+        for (t, count) in self.ranking() {
+            write!(f, " {t:?} {count}")?;
+            if let Some(evolved) = self.evolved.get(&t) {
+                write!(f, " ({evolved} evolved)")?;
+            }
+            if let Some(terastallized) = self.terastallized.get(&t) {
+                write!(f, " ({terastallized} tera)")?;
+            }
+            write!(f, " |")?;
+        }
+        if let Some(genome) = &self.genome {
+            write!(
+                f,
+                " Average genome: attack {:.3}, defense {:.3}, aggression {:.3}, mutation rate {:.3}",
+                genome.attack, genome.defense, genome.aggression, genome.mutation_rate
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::engine::cell::Type;
+use rand::{rngs::StdRng, Rng};
+
+/// Rule letting a cell change its defensive type once in its lifetime.
+#[derive(Debug, Clone, Copy)]
+pub struct Tera {
+    /// Chance, each time the cell survives an attack, that it terastallizes.
+    pub chance: f32,
+    /// Health ratio under which a surviving cell always terastallizes.
+    pub low_health: f32,
+    /// Type taken when terastallizing, random if unset.
+    pub target: Option<Type>,
+}
+
+impl Tera {
+    /// Whether a cell left with `health` out of `max_health` terastallizes now.
+    pub fn triggers(&self, health: f32, max_health: f32, rng: &mut StdRng) -> bool {
+        health / max_health <= self.low_health || rng.gen::<f32>() < self.chance
+    }
+
+    /// The type a cell takes when terastallizing.
+    pub fn pick_type(&self, rng: &mut StdRng) -> Type {
+        self.target
+            .unwrap_or_else(|| Type::TYPES[rng.gen_range(0..Type::TYPES.len())])
+    }
+}
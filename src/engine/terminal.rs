@@ -0,0 +1,241 @@
+use crate::engine::game::Game;
+use crate::engine::hud;
+use crate::engine::palette::Palette;
+use crate::engine::render::{Command, Frontend};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    style::{self, Colors, Print},
+    terminal, QueueableCommand,
+};
+use sdl2::pixels::Color;
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+/// Upper half of a character: its foreground is one cell, its background the one below.
+const HALF_BLOCK: char = '▀';
+
+/// Draws the board in the terminal with truecolor half blocks, two cells per character.
+///
+/// The last row of the terminal is a status line.
+pub struct Terminal {
+    out: Stdout,
+    pub palette: Palette,
+    /// Whether the board is shown one cell per half character from `offset`, rather than
+    /// scaled down to fit the terminal.
+    crop: bool,
+    /// Cell shown in the top left corner when cropping.
+    offset: (usize, usize),
+    /// Colors of every character last drawn, to only draw the ones that changed.
+    drawn: Vec<Option<(Color, Color)>>,
+    /// Size of the terminal when it was last drawn, in characters.
+    size: (u16, u16),
+    /// Why reading or drawing failed, if it did: the game then quits, and it is reported once
+    /// the terminal is given back.
+    error: Option<io::Error>,
+}
+
+impl Terminal {
+    /// Switches the terminal to a blank screen reading keys as they are pressed, until dropped.
+    pub fn setup() -> io::Result<Self> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        out.queue(terminal::EnterAlternateScreen)?
+            .queue(cursor::Hide)?
+            .flush()?;
+        Ok(Self {
+            out,
+            palette: Palette::default(),
+            crop: false,
+            offset: (0, 0),
+            drawn: Vec::new(),
+            size: (0, 0),
+            error: None,
+        })
+    }
+
+    /// Moves the cropped view by a quarter of the terminal in the given direction.
+    fn pan(&mut self, game: &Game, (dx, dy): (i32, i32)) {
+        let (columns, rows) = self.size;
+        let step = |offset: usize, view: usize, size: usize, d: i32| {
+            let moved = offset as i64 + d as i64 * (view / 4).max(1) as i64;
+            moved.clamp(0, size.saturating_sub(view) as i64) as usize
+        };
+        self.offset = (
+            step(self.offset.0, columns as usize, game.board.width, dx),
+            step(
+                self.offset.1,
+                rows.saturating_sub(1) as usize * 2,
+                game.board.height,
+                dy,
+            ),
+        );
+    }
+
+    fn draw(&mut self, game: &Game, tick_speed: f64, paused: bool) -> io::Result<()> {
+        let size = terminal::size()?;
+        if size != self.size || self.drawn.is_empty() {
+            self.size = size;
+            self.drawn = vec![None; size.0 as usize * size.1 as usize];
+            self.out
+                .queue(style::ResetColor)?
+                .queue(terminal::Clear(terminal::ClearType::All))?;
+        }
+        let (columns, rows) = (size.0 as usize, size.1.saturating_sub(1) as usize);
+        let (width, height) = (game.board.width, game.board.height);
+
+        // When scaling, every character covers the same number of cells in both directions
+        let scale = if self.crop {
+            1.0
+        } else {
+            (width as f64 / columns as f64)
+                .max(height as f64 / (rows * 2) as f64)
+                .max(1.0)
+        };
+        let cell_at = |column: usize, row: usize| {
+            let (x, y) = if self.crop {
+                (self.offset.0 + column, self.offset.1 + row)
+            } else {
+                (
+                    (column as f64 * scale) as usize,
+                    (row as f64 * scale) as usize,
+                )
+            };
+            (x < width && y < height)
+                .then(|| self.palette.cell_color(game.board.cells[x + y * width]))
+        };
+
+        for row in 0..rows {
+            // Skipping unchanged characters moves the cursor, it then has to be moved back
+            let mut cursor_moved = true;
+            for column in 0..columns {
+                let colors = (
+                    cell_at(column, row * 2).unwrap_or(Color::BLACK),
+                    cell_at(column, row * 2 + 1).unwrap_or(Color::BLACK),
+                );
+                let drawn = &mut self.drawn[column + row * columns];
+                if *drawn == Some(colors) {
+                    cursor_moved = true;
+                    continue;
+                }
+                *drawn = Some(colors);
+                if cursor_moved {
+                    self.out.queue(cursor::MoveTo(column as u16, row as u16))?;
+                    cursor_moved = false;
+                }
+                self.out
+                    .queue(style::SetColors(Colors::new(
+                        term_color(colors.0),
+                        term_color(colors.1),
+                    )))?
+                    .queue(Print(HALF_BLOCK))?;
+            }
+        }
+
+        let mut status = format!(
+            "Tick {} | {} ticks/s{} | {} | Space pause, N step, +/- speed, C {}, Q quit",
+            game.tick_count,
+            hud::speed_label(tick_speed),
+            if paused { " [paused]" } else { "" },
+            if self.crop { "cropped" } else { "scaled" },
+            if self.crop { "scale" } else { "crop" },
+        );
+        status.truncate(columns);
+        self.out
+            .queue(cursor::MoveTo(0, rows as u16))?
+            .queue(style::ResetColor)?
+            .queue(terminal::Clear(terminal::ClearType::CurrentLine))?
+            .queue(Print(status))?
+            .flush()
+    }
+
+    /// Adds a command for every pending key press, until none is left or reading fails.
+    fn read_keys(&mut self, game: &Game, commands: &mut Vec<Command>) -> io::Result<()> {
+        while event::poll(Duration::ZERO)? {
+            let key = match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                Event::Resize(..) => {
+                    self.drawn.clear();
+                    continue;
+                }
+                _ => continue,
+            };
+            match key.code {
+                // Raw mode catches Ctrl+C, which would otherwise have ended the program
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    commands.push(Command::Quit)
+                }
+                KeyCode::Esc | KeyCode::Char('q') => commands.push(Command::Quit),
+                KeyCode::Char(' ') | KeyCode::Char('p') => commands.push(Command::TogglePause),
+                KeyCode::Char('n') => commands.push(Command::Step),
+                KeyCode::Char('+') | KeyCode::Char('=') => commands.push(Command::SpeedUp),
+                KeyCode::Char('-') => commands.push(Command::SlowDown),
+                KeyCode::Char('c') => {
+                    self.crop = !self.crop;
+                    self.drawn.clear();
+                }
+                KeyCode::Left => self.pan(game, (-1, 0)),
+                KeyCode::Right => self.pan(game, (1, 0)),
+                KeyCode::Up => self.pan(game, (0, -1)),
+                KeyCode::Down => self.pan(game, (0, 1)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Frontend for Terminal {
+    /// Turns pending key presses into commands for the game loop, quitting once the terminal
+    /// failed.
+    fn poll_commands(&mut self, game: &Game) -> Vec<Command> {
+        let mut commands: Vec<Command> = Vec::new();
+        if self.error.is_none() {
+            if let Err(e) = self.read_keys(game, &mut commands) {
+                self.error = Some(e);
+            }
+        }
+        if self.error.is_some() {
+            commands.push(Command::Quit);
+        }
+        commands
+    }
+
+    /// Draws the characters that changed since the last frame, then the status line.
+    fn present(&mut self, game: &Game, tick_speed: f64, paused: bool) {
+        if self.error.is_none() {
+            if let Err(e) = self.draw(game, tick_speed, paused) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn uses_stdout(&self) -> bool {
+        true
+    }
+}
+
+impl Drop for Terminal {
+    /// Gives the terminal back as it was, even when leaving after a panic.
+    fn drop(&mut self) {
+        let _ = self
+            .out
+            .queue(style::ResetColor)
+            .and_then(|out| out.queue(cursor::Show))
+            .and_then(|out| out.queue(terminal::LeaveAlternateScreen))
+            .and_then(|out| out.flush());
+        let _ = terminal::disable_raw_mode();
+        if let Some(e) = &self.error {
+            // Standard error may be gone with the terminal, which must not panic here
+            let _ = writeln!(io::stderr(), "The terminal stopped working: {e}");
+        }
+    }
+}
+
+fn term_color(color: Color) -> style::Color {
+    style::Color::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
@@ -1,10 +1,14 @@
 mod engine;
 
+use engine::game::{MAX_FRAME_RATE, MAX_TICK_SPEED, MIN_FRAME_RATE, MIN_TICK_SPEED};
+use std::io::IsTerminal;
+
 const DEFAULT_WIDTH_WINDOW: u32 = 500;
 const DEFAULT_HEIGHT_WINDOW: u32 = 500;
 const DEFAULT_WIDTH_WORLD: usize = 100;
 const DEFAULT_HEIGHT_WORLD: usize = 100;
 const DEFAULT_TICK_SPEED: f64 = 60.0;
+const DEFAULT_FRAME_RATE: f64 = 60.0;
 
 fn main() -> Result<(), i32> {
     let mut win_width: u32 = DEFAULT_WIDTH_WINDOW;
@@ -12,6 +16,21 @@ fn main() -> Result<(), i32> {
     let mut world_width: usize = DEFAULT_WIDTH_WORLD;
     let mut world_height: usize = DEFAULT_HEIGHT_WORLD;
     let mut tick_speed: f64 = DEFAULT_TICK_SPEED;
+    let mut frame_rate: f64 = DEFAULT_FRAME_RATE;
+    let mut vsync = false;
+    let mut rules = engine::rules::Rules::default();
+    let mut stats_interval: Option<u64> = None;
+    let mut seed: u64 = rand::random();
+    let mut letterbox = false;
+    let mut palette = engine::palette::Palette::default();
+    let mut patterns = false;
+    let mut renderer_kind = RendererKind::Auto;
+    let mut until: Option<u64> = None;
+    let mut screenshot_at: Option<u64> = None;
+    let mut screenshot_scale: usize = 1;
+    let mut record: Option<(String, u64)> = None;
+    let mut record_scale: usize = 1;
+    let mut record_max: Option<usize> = None;
 
     let argv: Vec<String> = std::env::args().collect();
     let argc = argv.len();
@@ -71,10 +90,322 @@ fn main() -> Result<(), i32> {
                     print_help(HelpType::TickSpeed);
                     return Err(1);
                 }
-                tick_speed = match argv[i + 1].parse::<f64>() {
-                    Ok(t) => t,
+                // "max" runs the game as fast as possible
+                tick_speed = match argv[i + 1].as_str() {
+                    "max" => f64::INFINITY,
+                    t => match t.parse::<f64>() {
+                        Ok(t) if (MIN_TICK_SPEED..=MAX_TICK_SPEED).contains(&t) => t,
+                        _ => {
+                            print_help(HelpType::TickSpeed);
+                            return Err(1);
+                        }
+                    },
+                };
+                i += 2;
+            }
+            "--fps" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::Fps);
+                    return Err(1);
+                }
+                frame_rate = match argv[i + 1].parse::<f64>() {
+                    Ok(f) if (MIN_FRAME_RATE..=MAX_FRAME_RATE).contains(&f) => f,
+                    _ => {
+                        print_help(HelpType::Fps);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--vsync" => {
+                vsync = true;
+                i += 1;
+            }
+            "--moves" | "-m" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::Moves);
+                    return Err(1);
+                }
+                rules.moveset_size = match argv[i + 1].parse::<usize>() {
+                    Ok(m) if (1..=engine::cell::MAX_MOVES).contains(&m) => m,
+                    _ => {
+                        print_help(HelpType::Moves);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--move-policy" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::MovePolicy);
+                    return Err(1);
+                }
+                rules.move_policy = match argv[i + 1].parse::<engine::rules::MovePolicy>() {
+                    Ok(p) => p,
+                    Err(_) => {
+                        print_help(HelpType::MovePolicy);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--evolve" | "-e" => {
+                // Takes every following number as a conquest threshold
+                let mut thresholds: Vec<u32> = Vec::new();
+                while let Some(arg) = argv.get(i + 1 + thresholds.len()) {
+                    match arg.parse::<u32>() {
+                        Ok(t) => thresholds.push(t),
+                        Err(_) => break,
+                    }
+                }
+                if thresholds.is_empty() || !thresholds.windows(2).all(|w| w[0] < w[1]) {
+                    print_help(HelpType::Evolve);
+                    return Err(1);
+                }
+                i += 1 + thresholds.len();
+                rules.evolution = Some(engine::evolution::Evolution::from_thresholds(&thresholds));
+            }
+            "--genomes" | "-g" => {
+                rules.genomes = true;
+                i += 1;
+            }
+            "--tera" => {
+                // Chance and health threshold, then an optional type
+                if i + 2 >= argv.len() {
+                    print_help(HelpType::Tera);
+                    return Err(1);
+                }
+                let (chance, low_health) =
+                    match (argv[i + 1].parse::<f32>(), argv[i + 2].parse::<f32>()) {
+                        (Ok(c), Ok(l)) if (0.0..=1.0).contains(&c) && (0.0..=1.0).contains(&l) => {
+                            (c, l)
+                        }
+                        _ => {
+                            print_help(HelpType::Tera);
+                            return Err(1);
+                        }
+                    };
+                let target = argv
+                    .get(i + 3)
+                    .and_then(|t| engine::cell::Type::from_name(t));
+                rules.tera = Some(engine::tera::Tera {
+                    chance,
+                    low_health,
+                    target,
+                });
+                i += if target.is_some() { 4 } else { 3 };
+            }
+            "--inverse" | "-i" => {
+                rules.inverse = true;
+                i += 1;
+            }
+            "--seed" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::Seed);
+                    return Err(1);
+                }
+                seed = match argv[i + 1].parse::<u64>() {
+                    Ok(s) => s,
+                    Err(_) => {
+                        print_help(HelpType::Seed);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--cycles" | "-c" => {
+                // Uses the given schedule file, or the default one
+                match argv.get(i + 1).filter(|a| !a.starts_with('-')) {
+                    Some(path) => {
+                        let schedule = std::fs::read_to_string(path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|text| engine::cycle::Cycle::parse_schedule(&text));
+                        rules.cycles = match schedule {
+                            Ok(cycles) => cycles,
+                            Err(e) => {
+                                println!("Could not load schedule {path}: {e}");
+                                print_help(HelpType::Cycles);
+                                return Err(1);
+                            }
+                        };
+                        i += 2;
+                    }
+                    None => {
+                        rules.cycles = engine::cycle::Cycle::default_schedule();
+                        i += 1;
+                    }
+                }
+            }
+            "--letterbox" | "-l" => {
+                letterbox = true;
+                i += 1;
+            }
+            "--palette" => {
+                // A built-in palette's name, or a palette file
+                let Some(name) = argv.get(i + 1) else {
+                    print_help(HelpType::Palette);
+                    return Err(1);
+                };
+                palette = match engine::palette::Palette::named(name) {
+                    Some(p) => p,
+                    None => {
+                        let loaded = std::fs::read_to_string(name)
+                            .map_err(|e| e.to_string())
+                            .and_then(|text| engine::palette::Palette::parse(&text));
+                        match loaded {
+                            Ok(p) => p,
+                            Err(e) => {
+                                println!("Could not load palette {name}: {e}");
+                                print_help(HelpType::Palette);
+                                return Err(1);
+                            }
+                        }
+                    }
+                };
+                i += 2;
+            }
+            "--patterns" => {
+                patterns = true;
+                i += 1;
+            }
+            "--renderer" => {
+                renderer_kind = match argv.get(i + 1).map(|r| r.as_str()) {
+                    Some("window") => RendererKind::Window,
+                    Some("terminal") => RendererKind::Terminal,
+                    Some("null") => RendererKind::Null,
+                    Some("images") => {
+                        // A directory, then optionally how many ticks between two images
+                        let Some(directory) = argv.get(i + 2) else {
+                            print_help(HelpType::Renderer);
+                            return Err(1);
+                        };
+                        match argv.get(i + 3).map(|n| n.parse::<u64>()) {
+                            Some(Ok(n)) if n > 0 => {
+                                i += 1;
+                                RendererKind::Images(directory.clone(), n)
+                            }
+                            Some(Ok(_)) => {
+                                print_help(HelpType::Renderer);
+                                return Err(1);
+                            }
+                            _ => RendererKind::Images(directory.clone(), 1),
+                        }
+                    }
+                    _ => {
+                        print_help(HelpType::Renderer);
+                        return Err(1);
+                    }
+                };
+                i += if matches!(renderer_kind, RendererKind::Images(..)) {
+                    3
+                } else {
+                    2
+                };
+            }
+            "--ticks" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::Ticks);
+                    return Err(1);
+                }
+                until = match argv[i + 1].parse::<u64>() {
+                    Ok(t) if t > 0 => Some(t),
+                    _ => {
+                        print_help(HelpType::Ticks);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--screenshot-at" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::ScreenshotAt);
+                    return Err(1);
+                }
+                screenshot_at = match argv[i + 1].parse::<u64>() {
+                    Ok(t) => Some(t),
                     Err(_) => {
-                        print_help(HelpType::TickSpeed);
+                        print_help(HelpType::ScreenshotAt);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--screenshot-scale" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::ScreenshotScale);
+                    return Err(1);
+                }
+                screenshot_scale = match argv[i + 1].parse::<usize>() {
+                    Ok(s) if s > 0 => s,
+                    _ => {
+                        print_help(HelpType::ScreenshotScale);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--record" => {
+                // A .gif or .png file, then optionally how many ticks between two frames
+                let Some(path) = argv.get(i + 1) else {
+                    print_help(HelpType::Record);
+                    return Err(1);
+                };
+                if engine::export::AnimationFormat::from_path(path).is_none() {
+                    print_help(HelpType::Record);
+                    return Err(1);
+                }
+                match argv.get(i + 2).map(|n| n.parse::<u64>()) {
+                    Some(Ok(n)) if n > 0 => {
+                        record = Some((path.clone(), n));
+                        i += 3;
+                    }
+                    Some(Ok(_)) => {
+                        print_help(HelpType::Record);
+                        return Err(1);
+                    }
+                    _ => {
+                        record = Some((path.clone(), 1));
+                        i += 2;
+                    }
+                }
+            }
+            "--record-scale" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::RecordScale);
+                    return Err(1);
+                }
+                record_scale = match argv[i + 1].parse::<usize>() {
+                    Ok(s) if s > 0 => s,
+                    _ => {
+                        print_help(HelpType::RecordScale);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--record-max" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::RecordMax);
+                    return Err(1);
+                }
+                record_max = match argv[i + 1].parse::<usize>() {
+                    Ok(m) if m > 0 => Some(m),
+                    _ => {
+                        print_help(HelpType::RecordMax);
+                        return Err(1);
+                    }
+                };
+                i += 2;
+            }
+            "--stats" => {
+                if i + 1 >= argv.len() {
+                    print_help(HelpType::Stats);
+                    return Err(1);
+                }
+                stats_interval = match argv[i + 1].parse::<u64>() {
+                    Ok(s) if s > 0 => Some(s),
+                    _ => {
+                        print_help(HelpType::Stats);
                         return Err(1);
                     }
                 };
@@ -87,23 +418,168 @@ fn main() -> Result<(), i32> {
         }
     }
 
-    println!("Running game with the following parameters:\n\
+    println!(
+        "Running game with the following parameters:\n\
     Window size: {win_width}x{win_height}\n\
     World size: {world_width}x{world_height}\n\
-    Tick speed: {tick_speed}");
+    Tick speed: {}\n\
+    Frame rate: {}\n\
+    Seed: {seed}",
+        engine::hud::speed_label(tick_speed),
+        if vsync {
+            "vsync".to_string()
+        } else {
+            frame_rate.to_string()
+        }
+    );
+    for line in rules.summary() {
+        println!("{line}");
+    }
 
-    let mut game = engine::game::Game::new((world_width, world_height), (win_width, win_height));
-    game.game_loop(tick_speed);
+    let game = engine::game::Game::new((world_width, world_height), rules, seed);
+    let fallback = matches!(renderer_kind, RendererKind::Auto);
+    let screenshot_palette = palette.clone();
+    let record_palette = palette.clone();
+    let mut captures: Vec<Box<dyn engine::export::Capture>> = Vec::new();
+    let frontend: Box<dyn engine::render::Frontend> = match renderer_kind {
+        RendererKind::Auto | RendererKind::Window => {
+            let setup = engine::render::Renderer::setup(
+                (win_width, win_height),
+                (world_width, world_height),
+                vsync,
+            );
+            match setup {
+                Ok(mut renderer) => {
+                    renderer.letterbox = letterbox;
+                    renderer.palette = palette;
+                    renderer.patterns = patterns;
+                    renderer.screenshot_scale = screenshot_scale;
+                    Box::new(renderer)
+                }
+                // Without a display, the terminal is the next best thing, if there is one
+                Err(e @ engine::render::SetupError::NoDisplay(_)) if fallback => {
+                    eprintln!("Could not open a window: {e}");
+                    let terminal = std::io::stdout()
+                        .is_terminal()
+                        .then(engine::terminal::Terminal::setup)
+                        .and_then(|t| t.ok());
+                    match terminal {
+                        Some(mut terminal) => {
+                            terminal.palette = palette;
+                            Box::new(terminal)
+                        }
+                        // Without a view, a game that never ends and prints nothing is no use
+                        None if until.is_none() && stats_interval.is_none() => {
+                            eprintln!("Nothing can show the game, run it with --ticks or --stats");
+                            return Err(1);
+                        }
+                        None => {
+                            println!("Running without a view");
+                            Box::new(engine::headless::Null { until })
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not open a window: {e}");
+                    return Err(1);
+                }
+            }
+        }
+        RendererKind::Terminal => {
+            let mut terminal = match engine::terminal::Terminal::setup() {
+                Ok(t) => t,
+                Err(e) => {
+                    eprintln!("Could not set up the terminal: {e}");
+                    return Err(1);
+                }
+            };
+            terminal.palette = palette;
+            Box::new(terminal)
+        }
+        RendererKind::Images(directory, interval) => {
+            let mut images = match engine::headless::ImageSequence::new(directory.clone(), interval)
+            {
+                Ok(images) => images,
+                Err(e) => {
+                    eprintln!("Could not create {directory}: {e}");
+                    return Err(1);
+                }
+            };
+            images.palette = palette;
+            captures.push(Box::new(images));
+            Box::new(engine::headless::Null { until })
+        }
+        RendererKind::Null => Box::new(engine::headless::Null { until }),
+    };
+    if let Some(at) = screenshot_at {
+        captures.push(Box::new(engine::export::Screenshot {
+            at,
+            scale: screenshot_scale,
+            palette: screenshot_palette,
+        }));
+    }
+    if let Some((path, interval)) = record {
+        let mut recording = match engine::export::Recording::new(
+            path.clone(),
+            (world_width, world_height),
+            record_scale,
+            record_palette,
+        ) {
+            Ok(recording) => recording,
+            Err(e) => {
+                eprintln!("Could not record to {path}: {e}");
+                return Err(1);
+            }
+        };
+        recording.interval = interval;
+        recording.max_frames = record_max;
+        captures.push(Box::new(recording));
+    }
+    game.game_loop(
+        frontend,
+        captures,
+        tick_speed,
+        frame_rate,
+        stats_interval,
+        until,
+    );
 
     Ok(())
 }
 
+/// Which frontend shows the game.
+enum RendererKind {
+    /// A window, or whatever works without a display if there is none.
+    Auto,
+    Window,
+    Terminal,
+    /// Writes images to the directory, every that many ticks.
+    Images(String, u64),
+    Null,
+}
+
 enum HelpType<'a> {
     General,
     Invalid(&'a str),
     Window,
     Size,
     TickSpeed,
+    Fps,
+    Moves,
+    MovePolicy,
+    Evolve,
+    Tera,
+    Seed,
+    Cycles,
+    Palette,
+    Renderer,
+    Ticks,
+    ScreenshotAt,
+    ScreenshotScale,
+    Record,
+    RecordScale,
+    RecordMax,
+    Stats,
 }
 
 fn print_help(help: HelpType) {
@@ -114,7 +590,51 @@ fn print_help(help: HelpType) {
             --help -> Prints this message.\n\
             --window x y -> Sets the width and height of the window, respectively.\n\
             --size x y -> Sets the width and height of the world, respectively.\n\
-            --tickspeed x -> Sets the speed of the game, the higher is x, the faster is the game. |CAREFUL: BIG VALUES WILL BREAK|")
+            --tickspeed x -> Sets how many ticks per second the game runs, or max to run it as fast as possible.\n\
+            --fps x -> Sets how many frames per second are drawn, whatever the tick speed.\n\
+            --vsync -> Draws frames as the display refreshes.\n\
+            --moves x -> Sets how many moves a cell knows, its own type included (1 to 4).\n\
+            --move-policy p -> Sets how a cell picks its move: best, random or first.\n\
+            --evolve x y... -> Cells evolve after x conquests, then y, and so on.\n\
+            --genomes -> Cells carry a genome inherited, with mutations, by the cells they conquer.\n\
+            --tera c h [type] -> Cells terastallize once, with a c chance when hit or under h health ratio.\n\
+            --inverse -> Inverse Battle: the type chart is inverted.\n\
+            --seed x -> Sets the seed of the random generator, to replay a run.\n\
+            --cycles [file] -> Some types get stronger depending on the time, following the schedule file if given.\n\
+            --letterbox -> Keeps the board's aspect ratio, with black bars around it.\n\
+            --palette p -> Colors the types with a palette: default, okabe-ito, tol (colorblind-safe) or a file.\n\
+            --patterns -> Draws a pattern per type over big enough cells, to tell types apart without colors.\n\
+            --renderer r -> Shows the game in a window, or in the terminal (Space, N, +, - and Q keys).\n\
+            By default, a window, or the terminal when there is no display, or nothing given --ticks or --stats.\n\
+            --renderer images dir [x] -> Writes the board as an image to dir every x ticks instead.\n\
+            --renderer null -> Shows nothing, for runs only followed with --stats.\n\
+            --ticks x -> Stops the game after x ticks, quitting then with the images or null renderer.\n\
+            --screenshot-at x -> Saves the board as a PNG image once the game reaches tick x.\n\
+            --screenshot-scale x -> Draws cells as x by x pixels in saved images.\n\
+            --record file [x] -> Records the board every x ticks to an animated .gif or .png file.\n\
+            --record-scale x -> Draws cells as x by x pixels in the recording.\n\
+            --record-max x -> Saves the recording after x frames, instead of when quitting.\n\
+            --stats x -> Prints population statistics every x ticks.\n\
+            ==-==-==-==-==-==-==-==-==-==-==-==-==-==-==-==-==-==-\n\
+            In the window:\n\
+            Space -> Pauses or resumes the game.\n\
+            N, Right -> Runs a single tick.\n\
+            + / -, Up / Down -> Speeds up or slows down the game.\n\
+            Left click -> Paints cells of the selected type.\n\
+            1 to 0, [ / ] -> Selects the type to paint.\n\
+            , / . -> Shrinks or grows the brush.\n\
+            B -> Switches between a square and a round brush.\n\
+            Mouse wheel -> Zooms in or out.\n\
+            Right click drag, Shift + arrows -> Moves the view around.\n\
+            Home -> Shows the whole board again.\n\
+            M -> Switches what the colors show: types, health, age, conquests or fronts.\n\
+            E -> Saves the board as shown as a PNG image.\n\
+            T -> Shows or hides the types' patterns.\n\
+            H -> Shows or hides the HUD.\n\
+            L -> Shows or hides the legend, click a type in it to highlight its cells.\n\
+            I -> Describes the cell under the mouse and its neighbors.\n\
+            A -> Keeps the board's aspect ratio, or stretches it to the window.\n\
+            Escape -> Quits.")
         }
         HelpType::Invalid(s) => {
             println!(
@@ -136,9 +656,111 @@ fn print_help(help: HelpType) {
         }
         HelpType::TickSpeed => {
             println!(
-                "You must input a valid non zero number! (big numbers will break the program)\n\
+                "You must input a number from {MIN_TICK_SPEED} to {MAX_TICK_SPEED}, or max!\n\
             Example: --tickspeed 60"
             );
         }
+        HelpType::Fps => {
+            println!(
+                "You must input a number from {MIN_FRAME_RATE} to {MAX_FRAME_RATE}!\n\
+            Example: --fps 30"
+            );
+        }
+        HelpType::Moves => {
+            println!(
+                "You must input a number between 1 and 4!\n\
+            Example: --moves 4"
+            );
+        }
+        HelpType::MovePolicy => {
+            println!(
+                "You must input one of: best, random, first!\n\
+            Example: --move-policy best"
+            );
+        }
+        HelpType::Evolve => {
+            println!(
+                "You must input at least one number, in increasing order!\n\
+            Example: --evolve 5 20"
+            );
+        }
+        HelpType::Tera => {
+            println!(
+                "You must input a chance and a health ratio between 0 and 1, optionally followed by a type!\n\
+            Example: --tera 0.01 0.3 fairy"
+            );
+        }
+        HelpType::Seed => {
+            println!(
+                "You must input a valid positive number!\n\
+            Example: --seed 42"
+            );
+        }
+        HelpType::Cycles => {
+            println!(
+                "You must input a valid schedule file, made of cycles and their phases!\n\
+            Example: --cycles schedule.txt, with schedule.txt containing:\n\
+            cycle time\n\
+            day 300\n\
+            night 300 dark=1.5 ghost=1.5"
+            );
+        }
+        HelpType::Palette => {
+            println!(
+                "You must input default, okabe-ito, tol, or a valid palette file!\n\
+            Example: --palette colors.txt, with colors.txt containing:\n\
+            base okabe-ito\n\
+            fire 255 80 0\n\
+            water 0 90 255"
+            );
+        }
+        HelpType::Renderer => {
+            println!(
+                "You must input window, terminal, null, or images followed by a directory!\n\
+            Example: --renderer images frames 10"
+            );
+        }
+        HelpType::Ticks => {
+            println!(
+                "You must input a valid non zero number!\n\
+            Example: --ticks 1000"
+            );
+        }
+        HelpType::ScreenshotAt => {
+            println!(
+                "You must input a valid positive number!\n\
+            Example: --screenshot-at 500"
+            );
+        }
+        HelpType::ScreenshotScale => {
+            println!(
+                "You must input a valid non zero number!\n\
+            Example: --screenshot-scale 4"
+            );
+        }
+        HelpType::Record => {
+            println!(
+                "You must input a .gif or .png file, optionally followed by a non zero number!\n\
+            Example: --record fronts.gif 10"
+            );
+        }
+        HelpType::RecordScale => {
+            println!(
+                "You must input a valid non zero number!\n\
+            Example: --record-scale 4"
+            );
+        }
+        HelpType::RecordMax => {
+            println!(
+                "You must input a valid non zero number!\n\
+            Example: --record-max 200"
+            );
+        }
+        HelpType::Stats => {
+            println!(
+                "You must input a valid non zero number!\n\
+            Example: --stats 100"
+            );
+        }
     }
 }